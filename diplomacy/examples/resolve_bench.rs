@@ -0,0 +1,132 @@
+//! Time the adjudication of a dense main-phase position with units from every power in
+//! contact, including supports, head-to-head battles, and convoys, along with convoy
+//! paradoxes from the DATC that stress convoy route calculation.
+//!
+//! Each position is resolved sequentially and partitioned into independent clusters; build
+//! with the `parallel` feature to also resolve those clusters on separate threads.
+//!
+//! Run with `cargo run --release --example resolve_bench [iterations]`.
+
+use std::time::Instant;
+
+use diplomacy::geo::standard_map;
+use diplomacy::judge::{MappedMainOrder, Outcome, Rulebook, Submission};
+
+const DENSE: &[&str] = &[
+    "ENG: F eng -> bre",
+    "ENG: F iri Supports F eng -> bre",
+    "ENG: F nth convoys yor -> nwy",
+    "ENG: F nwg Supports A yor -> nwy",
+    "ENG: A yor -> nwy",
+    "ENG: A lvp -> wal",
+    "FRA: A bre Hold",
+    "FRA: F mao Supports A bre",
+    "FRA: A par -> bur",
+    "FRA: A pic -> bel",
+    "FRA: F lyo -> tys",
+    "FRA: A mar Supports A par -> bur",
+    "GER: A mun -> bur",
+    "GER: A ruh Supports A mun -> bur",
+    "GER: A hol -> bel",
+    "GER: F kie -> den",
+    "GER: A ber -> sil",
+    "GER: F bal Supports F kie -> den",
+    "RUS: A war -> gal",
+    "RUS: A mos -> ukr",
+    "RUS: F sev -> bla",
+    "RUS: F swe -> nwy",
+    "RUS: A lvn -> pru",
+    "RUS: F bot Supports F swe -> nwy",
+    "AUS: A vie -> gal",
+    "AUS: A bud Supports A vie -> gal",
+    "AUS: F tri -> adr",
+    "AUS: A ser -> rum",
+    "AUS: A boh -> sil",
+    "ITA: A ven -> tri",
+    "ITA: A rom Hold",
+    "ITA: F tys Hold",
+    "ITA: F nap -> ion",
+    "ITA: A pie -> mar",
+    "TUR: F bla -> sev",
+    "TUR: A con -> bul",
+    "TUR: A arm Supports F bla -> sev",
+    "TUR: A smy -> con",
+];
+
+/// DATC 6.F.20
+const MULTI_ROUTE_PARADOX: &[&str] = &[
+    "FRA: A tun -> nap",
+    "FRA: F tys convoys tun -> nap",
+    "ITA: F nap Supports F ion",
+    "ITA: F ion convoys tun -> nap",
+    "TUR: F aeg Supports F eas -> ion",
+    "TUR: F eas -> ion",
+];
+
+/// DATC 6.F.24
+const SECOND_ORDER_PARADOX: &[&str] = &[
+    "ENG: F edi -> nth",
+    "ENG: F lon Supports F edi -> nth",
+    "ENG: F iri -> eng",
+    "ENG: F mao Supports F iri -> eng",
+    "FRA: A bre -> lon",
+    "FRA: F eng convoys bre -> lon",
+    "FRA: F bel Supports F eng",
+    "RUS: A nwy -> bel",
+    "RUS: F nth convoys nwy -> bel",
+];
+
+fn main() {
+    let iterations = std::env::args()
+        .nth(1)
+        .map(|arg| arg.parse::<u32>().expect("Iterations should be a number"))
+        .unwrap_or(2_000);
+
+    for (name, orders) in [
+        ("dense", DENSE),
+        ("6.F.20", MULTI_ROUTE_PARADOX),
+        ("6.F.24", SECOND_ORDER_PARADOX),
+    ] {
+        let orders = orders
+            .iter()
+            .map(|ord| ord.parse::<MappedMainOrder>().unwrap())
+            .collect::<Vec<_>>();
+
+        bench(name, "sequential", &orders, iterations, |s| {
+            s.adjudicate(Rulebook::default())
+        });
+        bench(name, "partitioned", &orders, iterations, |s| {
+            s.adjudicate_partitioned(Rulebook::default())
+        });
+        #[cfg(feature = "parallel")]
+        bench(name, "parallel", &orders, iterations, |s| {
+            s.adjudicate_parallel(Rulebook::default())
+        });
+    }
+}
+
+fn bench(
+    name: &str,
+    mode: &str,
+    orders: &[MappedMainOrder],
+    iterations: u32,
+    adjudicate: impl for<'a> Fn(&'a Submission) -> Outcome<'a, Rulebook>,
+) {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        let submission = Submission::with_inferred_state(standard_map(), orders.to_vec());
+        let outcome = adjudicate(&submission);
+        assert_eq!(outcome.all_orders().count(), orders.len());
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "{} ({}): resolved {} orders {} times in {:?} ({:?} per turn)",
+        name,
+        mode,
+        orders.len(),
+        iterations,
+        elapsed,
+        elapsed / iterations
+    );
+}
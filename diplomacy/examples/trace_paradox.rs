@@ -0,0 +1,32 @@
+//! Print every adjudication the resolver performs while judging the simple convoy
+//! paradox from DATC 6.F.14.
+//!
+//! Run with `cargo run --example trace_paradox`.
+
+use diplomacy::geo::standard_map;
+use diplomacy::judge::{MappedMainOrder, Rulebook, Submission, Tracing};
+
+fn main() {
+    let orders = [
+        "ENG: F lon supports F wal -> eng",
+        "ENG: F wal -> eng",
+        "FRA: A bre -> lon",
+        "FRA: F eng convoys bre -> lon",
+    ]
+    .into_iter()
+    .map(|ord| ord.parse::<MappedMainOrder>().unwrap())
+    .collect();
+
+    let submission = Submission::with_inferred_state(standard_map(), orders);
+    let outcome = submission.adjudicate(Tracing::new(Rulebook::default()));
+
+    println!("Trace:");
+    for (step, entry) in outcome.rules().entries().iter().enumerate() {
+        println!("  {:>3}. {} => {:?}", step + 1, entry.order, entry.result);
+    }
+
+    println!("Outcome:");
+    for order in submission.submitted_orders() {
+        println!("  {} => {:?}", order, outcome.get(order).unwrap());
+    }
+}
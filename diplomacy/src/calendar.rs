@@ -4,7 +4,8 @@
 //! at the conclusion of a turn.
 
 use crate::time::{Phase, Season, Time};
-use std::collections::BTreeSet;
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
 
 /// Identifier of a specific turn in a game.
 pub type Month = (Season, Phase);
@@ -79,7 +80,7 @@ impl Calendar {
     }
 
     /// Create an infinite iterator that goes through all past and future `Time` values in the game.
-    pub fn iter(&self) -> Iter {
+    pub fn iter(&self) -> Iter<'_> {
         Iter {
             calendar: self,
             step: 0,
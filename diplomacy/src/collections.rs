@@ -0,0 +1,20 @@
+//! Hashed collections used by the crate.
+//!
+//! With `std` these are the standard library's collections. Without it they come from
+//! `hashbrown` using the FNV hasher, since `alloc` has no hashed collections and no
+//! source of randomness for seeding one.
+//!
+//! The hasher is fixed by these aliases, so create collections with `default()` rather
+//! than `new()`, which only exists for the standard library's hasher.
+
+#[cfg(feature = "std")]
+pub type HashMap<K, V> = std::collections::HashMap<K, V>;
+
+#[cfg(feature = "std")]
+pub type HashSet<T> = std::collections::HashSet<T>;
+
+#[cfg(not(feature = "std"))]
+pub type HashMap<K, V> = hashbrown::HashMap<K, V, fnv::FnvBuildHasher>;
+
+#[cfg(not(feature = "std"))]
+pub type HashSet<T> = hashbrown::HashSet<T, fnv::FnvBuildHasher>;
@@ -5,16 +5,36 @@
 //! 1. Call `ProvinceRegistry::finish()` and then add all regions to that function's return.
 //! 1. Call `RegionRegistry::finish()` and add all borders to that function's return.
 //! 1. Call `BorderRegistry::finish()` and use the resulting map.
+//!
+//! Use `BorderRegistry::finish_with_warnings()` instead to also check the map for likely
+//! mistakes; see `Map::validate()` for details.
+//!
+//! Maps can also be read from text listing each region's neighbors; see
+//! [`from_adjacency_reader`].
 
-use super::{Border, Coast, Map, Province, ProvinceKey, Region, Terrain};
-use crate::ShortName;
-use std::collections::HashMap;
+use super::standard::{supply_center_from_word, terrain_from_word};
+use super::{
+    Border, Coast, Map, MapWarning, Province, ProvinceKey, Region, RegionKey, SupplyCenter, Terrain,
+};
+use crate::collections::{HashMap, HashSet};
+use crate::{ShortName, UnitType};
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io::{self, BufRead, BufReader, Read};
 
 #[derive(Debug, Clone)]
 pub enum MapError {
     ProvinceNotFound,
     RegionNotFound,
     IncompatibleBorderTerrain,
+    /// The home supply center has no region that any unit can occupy, so nothing could ever
+    /// be built there.
+    UnoccupiableHomeSupplyCenter(String),
 }
 
 /// A collection of provinces that validates on insertion.
@@ -47,7 +67,7 @@ impl RegionRegistry {
     pub fn new(provinces: ProvinceRegistry) -> Self {
         RegionRegistry {
             provinces: provinces.provinces,
-            regions: HashMap::new(),
+            regions: HashMap::default(),
         }
     }
 
@@ -90,6 +110,7 @@ impl From<ProvinceRegistry> for RegionRegistry {
 /// A collection of provinces, regions, and borders that allows border insertion after validation.
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct BorderRegistry {
+    name: String,
     provinces: HashMap<String, Province>,
     regions: HashMap<String, Region>,
     borders: Vec<Border>,
@@ -99,12 +120,18 @@ impl BorderRegistry {
     /// Creates a new instance from a `RegionRegistry`.
     pub fn new(builder: RegionRegistry) -> Self {
         BorderRegistry {
+            name: String::new(),
             provinces: builder.provinces,
             regions: builder.regions,
             borders: vec![],
         }
     }
 
+    /// Set the name of the map, such as `"standard"`. Maps are unnamed by default.
+    pub fn set_name(&mut self, name: impl Into<String>) {
+        self.name = name.into();
+    }
+
     /// Register a border between two regions identified by key after validation.
     ///
     /// This function validates that:
@@ -128,19 +155,57 @@ impl BorderRegistry {
     }
 
     /// Convert the builder to an immutable Map instance.
-    pub fn finish(self) -> Map {
-        Map::from(self)
+    ///
+    /// This function validates that every home supply center has at least one region
+    /// a unit can occupy.
+    pub fn finish(self) -> Result<Map, MapError> {
+        Map::try_from(self)
+    }
+
+    /// Convert the builder to an immutable Map instance, returning any warnings
+    /// from [`Map::validate`] alongside it.
+    pub fn finish_with_warnings(self) -> Result<(Map, Vec<MapWarning>), MapError> {
+        let map = self.finish()?;
+        let warnings = map.validate();
+        Ok((map, warnings))
+    }
+
+    /// Check that every home supply center has a region which some unit can occupy.
+    pub(in crate::geo) fn validate_home_supply_centers(&self) -> Result<(), MapError> {
+        let mut homes = self
+            .provinces
+            .values()
+            .filter(|p| matches!(p.supply_center, SupplyCenter::Home(_)))
+            .collect::<Vec<_>>();
+        homes.sort_by(|a, b| a.short_name.cmp(&b.short_name));
+
+        for province in homes {
+            let occupiable = self.regions.values().any(|r| {
+                r.province() == province
+                    && (UnitType::Army.can_occupy(r.terrain())
+                        || UnitType::Fleet.can_occupy(r.terrain()))
+            });
+
+            if !occupiable {
+                return Err(MapError::UnoccupiableHomeSupplyCenter(
+                    province.short_name.clone(),
+                ));
+            }
+        }
+
+        Ok(())
     }
 
     /// Get a view of the contents in a format that `Map` can use.
     pub(in crate::geo) fn contents(
         self,
     ) -> (
+        String,
         HashMap<String, Province>,
         HashMap<String, Region>,
         Vec<Border>,
     ) {
-        (self.provinces, self.regions, self.borders)
+        (self.name, self.provinces, self.regions, self.borders)
     }
 
     /// Find a region by its canonical short name.
@@ -171,3 +236,278 @@ impl From<RegionRegistry> for BorderRegistry {
         rr.finish()
     }
 }
+
+/// The terrain of a border between regions of the given terrains when the adjacency text
+/// does not specify one: sea if either region is sea, land if either is land, and coast
+/// otherwise.
+pub(in crate::geo) fn default_border_terrain(r1: Terrain, r2: Terrain) -> Terrain {
+    if r1 == Terrain::Sea || r2 == Terrain::Sea {
+        Terrain::Sea
+    } else if r1 == Terrain::Land || r2 == Terrain::Land {
+        Terrain::Land
+    } else {
+        Terrain::Coast
+    }
+}
+
+/// An error reading a map with [`from_adjacency_reader`].
+#[derive(Debug)]
+pub struct AdjacencyError {
+    line: usize,
+    kind: AdjacencyErrorKind,
+}
+
+impl AdjacencyError {
+    fn new(line: usize, kind: AdjacencyErrorKind) -> Self {
+        Self { line, kind }
+    }
+
+    /// The 1-based number of the line with the error.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn kind(&self) -> &AdjacencyErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for AdjacencyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl core::error::Error for AdjacencyError {}
+
+/// The reason adjacency text could not be read as a map.
+#[derive(Debug)]
+pub enum AdjacencyErrorKind {
+    /// The text could not be read.
+    #[cfg(feature = "std")]
+    Io(io::Error),
+    /// The line names a region but not its terrain.
+    MissingTerrain,
+    /// The terrain is not `land`, `coast`, or `sea`.
+    UnknownTerrain(String),
+    /// The text is not a valid region name.
+    InvalidRegion(String),
+    /// The region was already listed on an earlier line.
+    DuplicateRegion(String),
+    /// The province was given a different supply center on an earlier line.
+    ConflictingSupplyCenter(String),
+    /// The neighbor is not listed as a region anywhere in the text.
+    UnknownNeighbor(String),
+    /// The neighbor was already listed on this region's line.
+    DuplicateBorder(String),
+    /// The neighbor does not list this region back, or lists it with a different terrain.
+    AsymmetricBorder(String),
+    /// The border could not be added to the map.
+    Map(MapError),
+}
+
+impl fmt::Display for AdjacencyErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use AdjacencyErrorKind::*;
+        match self {
+            #[cfg(feature = "std")]
+            Io(e) => write!(f, "{}", e),
+            MissingTerrain => write!(f, "missing terrain"),
+            UnknownTerrain(t) => write!(f, "unknown terrain `{}`", t),
+            InvalidRegion(r) => write!(f, "invalid region `{}`", r),
+            DuplicateRegion(r) => write!(f, "region `{}` is listed more than once", r),
+            ConflictingSupplyCenter(p) => {
+                write!(f, "province `{}` has conflicting supply centers", p)
+            }
+            UnknownNeighbor(r) => write!(f, "neighbor `{}` is not a listed region", r),
+            DuplicateBorder(r) => write!(f, "neighbor `{}` is listed more than once", r),
+            AsymmetricBorder(r) => write!(f, "neighbor `{}` does not list this border back", r),
+            Map(e) => write!(f, "{:?}", e),
+        }
+    }
+}
+
+/// Read a map from text listing each region and its neighbors.
+///
+/// Each line lists a region, its terrain (`land`, `coast`, or `sea`), an optional supply
+/// center annotation, and then the region's neighbors, separated by tabs or spaces. Text
+/// after `#` is a comment, and blank lines are ignored.
+///
+/// ```text
+/// # region  terrain  [supply center]  neighbors...
+/// par     land    sc=FRA      bur gas pic bre
+/// bel     coast   sc=neutral  eng hol nth pic bur ruh
+/// stp(nc) sea                 bar nwy
+/// ven     coast   sc=ITA      adr apu rom:land tus:land tri pie tyr
+/// ```
+///
+/// The supply center annotation is `sc=neutral`, or `sc=` followed by the nation for which
+/// the province is a home supply center. It may be given on any region of the province.
+///
+/// A border's terrain is sea if either region is sea, land if either region is land, and
+/// coast otherwise. Append `:land` or another terrain to a neighbor to override this, such as
+/// for coastal provinces that armies can move between but fleets cannot.
+///
+/// Every border must be listed on the lines of both of its regions, with the same terrain.
+/// The returned map is unnamed. [`Map::to_adjacency`] writes a map in this format.
+pub fn from_adjacency_str(text: &str) -> Result<Map, AdjacencyError> {
+    from_adjacency_lines(text.lines().map(Ok))
+}
+
+/// Read a map from an I/O source in the format described by [`from_adjacency_str`].
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+pub fn from_adjacency_reader(reader: impl Read) -> Result<Map, AdjacencyError> {
+    from_adjacency_lines(
+        BufReader::new(reader)
+            .lines()
+            .map(|line| line.map_err(AdjacencyErrorKind::Io)),
+    )
+}
+
+fn from_adjacency_lines(
+    lines: impl Iterator<Item = Result<impl AsRef<str>, AdjacencyErrorKind>>,
+) -> Result<Map, AdjacencyError> {
+    use AdjacencyErrorKind::*;
+
+    let mut regions = Vec::<(usize, RegionKey, Terrain)>::new();
+    let mut supply_centers = HashMap::<ProvinceKey, SupplyCenter>::default();
+    let mut listings = Vec::<(usize, RegionKey, String, Option<Terrain>)>::new();
+
+    for (index, line) in lines.enumerate() {
+        let line_number = index + 1;
+        let err = |kind| AdjacencyError::new(line_number, kind);
+        let line = line.map_err(err)?;
+        let content = line.as_ref().split('#').next().unwrap_or_default();
+        let mut words = content.split_whitespace().peekable();
+
+        let Some(region_word) = words.next() else {
+            continue;
+        };
+
+        let region = parse_region(region_word).map_err(&err)?;
+        if regions.iter().any(|(_, r, _)| *r == region) {
+            return Err(err(DuplicateRegion(region_word.to_string())));
+        }
+
+        let terrain_word = words.next().ok_or_else(|| err(MissingTerrain))?;
+        let terrain = terrain_from_word(terrain_word)
+            .map_err(|_| err(UnknownTerrain(terrain_word.to_string())))?;
+        regions.push((line_number, region.clone(), terrain));
+
+        if let Some(sc) = words.next_if(|w| w.starts_with("sc=")) {
+            let supply_center = supply_center_from_word(&sc["sc=".len()..]);
+            let province = region.province().clone();
+            match supply_centers.get(&province) {
+                Some(existing) if *existing != supply_center => {
+                    return Err(err(ConflictingSupplyCenter(
+                        province.short_name().into_owned(),
+                    )));
+                }
+                _ => {
+                    supply_centers.insert(province, supply_center);
+                }
+            }
+        }
+
+        let mut neighbors = HashSet::default();
+        for word in words {
+            let (neighbor, border_terrain) = match word.split_once(':') {
+                Some((neighbor, terrain)) => (
+                    neighbor,
+                    Some(
+                        terrain_from_word(terrain)
+                            .map_err(|_| err(UnknownTerrain(terrain.to_string())))?,
+                    ),
+                ),
+                None => (word, None),
+            };
+
+            if !neighbors.insert(neighbor) {
+                return Err(err(DuplicateBorder(neighbor.to_string())));
+            }
+
+            listings.push((
+                line_number,
+                region.clone(),
+                neighbor.to_string(),
+                border_terrain,
+            ));
+        }
+    }
+
+    let terrains = regions
+        .iter()
+        .map(|(_, region, terrain)| (region.clone(), *terrain))
+        .collect::<HashMap<_, _>>();
+    let mut borders = HashMap::<(RegionKey, RegionKey), Terrain>::default();
+    let mut ordered_borders = Vec::new();
+    for (line_number, region, neighbor_word, border_terrain) in &listings {
+        let err = |kind| AdjacencyError::new(*line_number, kind);
+        let neighbor = parse_region(neighbor_word).map_err(&err)?;
+        let Some(&neighbor_terrain) = terrains.get(&neighbor) else {
+            return Err(err(UnknownNeighbor(neighbor_word.clone())));
+        };
+
+        let terrain = border_terrain
+            .unwrap_or_else(|| default_border_terrain(terrains[region], neighbor_terrain));
+        borders.insert((region.clone(), neighbor.clone()), terrain);
+        ordered_borders.push((*line_number, region, neighbor, neighbor_word));
+    }
+
+    let mut province_registry = ProvinceRegistry::default();
+    let mut seen_provinces = HashSet::default();
+    for (line_number, region, _) in &regions {
+        if seen_provinces.insert(region.province()) {
+            province_registry
+                .register(Province {
+                    short_name: region.province().short_name().into_owned(),
+                    supply_center: supply_centers
+                        .get(region.province())
+                        .cloned()
+                        .unwrap_or(SupplyCenter::None),
+                })
+                .map_err(|e| AdjacencyError::new(*line_number, Map(e)))?;
+        }
+    }
+
+    let mut region_registry = province_registry.finish();
+    for (line_number, region, terrain) in &regions {
+        region_registry
+            .register(&region.province().short_name(), region.coast(), *terrain)
+            .map_err(|e| AdjacencyError::new(*line_number, Map(e)))?;
+    }
+
+    let mut border_registry = region_registry.finish();
+    let mut registered = HashSet::default();
+    for (line_number, region, neighbor, neighbor_word) in ordered_borders {
+        let err = |kind| AdjacencyError::new(line_number, kind);
+        let terrain = borders[&(region.clone(), neighbor.clone())];
+        if borders.get(&(neighbor.clone(), region.clone())) != Some(&terrain) {
+            return Err(err(AsymmetricBorder(neighbor_word.clone())));
+        }
+
+        let pair = if *region <= neighbor {
+            (region.clone(), neighbor)
+        } else {
+            (neighbor, region.clone())
+        };
+
+        if registered.insert(pair.clone()) {
+            border_registry
+                .register(&pair.0.short_name(), &pair.1.short_name(), terrain)
+                .map_err(|e| err(Map(e)))?;
+        }
+    }
+
+    // Every province was registered from one of its regions, so home supply centers
+    // always have a region to build in.
+    Ok(border_registry
+        .finish()
+        .expect("every province has a region"))
+}
+
+fn parse_region(word: &str) -> Result<RegionKey, AdjacencyErrorKind> {
+    word.parse()
+        .map_err(|_| AdjacencyErrorKind::InvalidRegion(word.to_string()))
+}
@@ -1,7 +1,7 @@
 use crate::ShortName;
 
-use std::fmt::Debug;
-use std::hash::Hash;
+use core::fmt::Debug;
+use core::hash::Hash;
 
 /// An addressable location in the Diplomacy world.
 /// This trait is used during order parsing and mapping to allow for
@@ -1,25 +1,77 @@
-use std::collections::HashMap;
+use crate::collections::{HashMap, HashSet};
+use core::fmt::Write;
 
+#[cfg(feature = "std")]
 use petgraph::graphmap::UnGraphMap;
 
-use super::{Border, Province, ProvinceKey, Region, RegionKey, Terrain};
-use crate::geo::builder::BorderRegistry;
+use super::{Border, Coast, Province, ProvinceKey, Region, RegionKey, SupplyCenter, Terrain};
+use crate::geo::builder::{default_border_terrain, BorderRegistry, MapError};
+use crate::parser::{Error, ErrorKind};
+use crate::{Nation, ShortName, UnitType};
+use alloc::{string::String, vec, vec::Vec};
 
 /// A collection of provinces, their constituent regions, and the interconnecting borders.
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct Map {
+    name: String,
     provinces: HashMap<String, Province>,
     regions: HashMap<String, Region>,
     borders: Vec<Border>,
 }
 
 impl Map {
+    /// The name of the map, such as `"standard"`, or an empty string if the map was built
+    /// without a name.
+    ///
+    /// Built-in maps can be looked up by name using [`map_by_name`](crate::geo::map_by_name).
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     /// Iterate through the provinces in the map. Each province will be returned exactly once,
     /// but order is unspecified.
     pub fn provinces(&self) -> impl Iterator<Item = &Province> {
         self.provinces.values()
     }
 
+    /// Iterate through the provinces whose overall terrain is `terrain`, in unspecified order.
+    ///
+    /// A province's terrain is that of its regions if they all share the same terrain. Provinces
+    /// whose regions have different terrain, such as Spain, are coastal.
+    pub fn provinces_by_terrain(&self, terrain: Terrain) -> impl Iterator<Item = &Province> {
+        self.provinces()
+            .filter(move |p| self.province_terrain(p) == Some(terrain))
+    }
+
+    /// Iterate through the provinces that only fleets can occupy.
+    pub fn sea_provinces(&self) -> impl Iterator<Item = &Province> {
+        self.provinces_by_terrain(Terrain::Sea)
+    }
+
+    /// Iterate through the provinces that only armies can occupy.
+    pub fn land_provinces(&self) -> impl Iterator<Item = &Province> {
+        self.provinces_by_terrain(Terrain::Land)
+    }
+
+    /// Iterate through the provinces that both armies and fleets can occupy.
+    pub fn coastal_provinces(&self) -> impl Iterator<Item = &Province> {
+        self.provinces_by_terrain(Terrain::Coast)
+    }
+
+    /// Get the overall terrain of a province, or `None` if it has no regions.
+    fn province_terrain(&self, province: &Province) -> Option<Terrain> {
+        let mut terrains = self
+            .regions()
+            .filter(|r| r.province() == province)
+            .map(|r| r.terrain());
+        let first = terrains.next()?;
+        if terrains.all(|t| t == first) {
+            Some(first)
+        } else {
+            Some(Terrain::Coast)
+        }
+    }
+
     /// Iterate through the regions in the map. Each region will be returned exactly once,
     /// but order is unspecified.
     pub fn regions(&self) -> impl Iterator<Item = &Region> {
@@ -31,6 +83,234 @@ impl Map {
         self.regions.get(short_name)
     }
 
+    /// Get the key of the region named by `short_name`, or `None` if the region isn't in the
+    /// map.
+    ///
+    /// Unlike parsing a [`RegionKey`], this only produces keys that exist in the map. Case is
+    /// ignored, and coasts may be written either as `spa(nc)` or `spa/nc`.
+    pub fn region_key(&self, short_name: &str) -> Option<RegionKey> {
+        let key = short_name.to_lowercase().parse::<RegionKey>().ok()?;
+        self.find_region(&key.short_name()).map(RegionKey::from)
+    }
+
+    /// Find the regions whose short names are most similar to `short_name`, for suggesting
+    /// corrections to a mistyped region. At most `max` regions are returned, most similar
+    /// first; regions more than two edits away from `short_name` are never suggested.
+    ///
+    /// Comparison ignores case.
+    pub fn suggest_regions(&self, short_name: &str, max: usize) -> Vec<&Region> {
+        const MAX_DISTANCE: usize = 2;
+
+        let short_name = short_name.to_lowercase();
+        let mut candidates = self
+            .regions()
+            .map(|r| {
+                (
+                    edit_distance(&short_name, &r.short_name().to_lowercase()),
+                    r,
+                )
+            })
+            .filter(|(distance, _)| *distance <= MAX_DISTANCE)
+            .collect::<Vec<_>>();
+        candidates.sort_by(|(d1, r1), (d2, r2)| {
+            d1.cmp(d2)
+                .then_with(|| r1.short_name().cmp(&r2.short_name()))
+        });
+        candidates.into_iter().take(max).map(|(_, r)| r).collect()
+    }
+
+    /// Find a region by its canonical short name, returning an error that suggests similar
+    /// regions if the region is not in the map.
+    ///
+    /// Combine with [`Order::try_map_region`](crate::Order::try_map_region) to check that
+    /// the regions in a parsed order exist before submitting it.
+    pub fn resolve_region<'a>(&'a self, short_name: &str) -> Result<&'a Region, Error> {
+        self.find_region(short_name).ok_or_else(|| {
+            let suggestions = self
+                .suggest_regions(short_name, 3)
+                .into_iter()
+                .map(|r| r.short_name().into_owned())
+                .collect();
+            Error::new(ErrorKind::UnknownRegion(suggestions), short_name)
+        })
+    }
+
+    /// Get the only region of a province, or `None` if the province has multiple coasts
+    /// or isn't in the map.
+    ///
+    /// Most provinces consist of a single region, so this is useful for working at province
+    /// granularity without needing to handle coasts.
+    pub fn sole_region(&self, province: &ProvinceKey) -> Option<&Region> {
+        let mut regions = self.regions().filter(|r| r.province() == province);
+        let region = regions.next()?;
+        if regions.next().is_some() {
+            None
+        } else {
+            Some(region)
+        }
+    }
+
+    /// Get the named coasts of a province, such as the north and south coasts of St Petersburg,
+    /// sorted in the order north, east, south, west.
+    ///
+    /// A province with named coasts has one region for each coast, which fleets occupy, and a
+    /// coastless region, which armies occupy. Most coastal provinces have no named coasts, so
+    /// this is empty for them, as it is for provinces that aren't in the map.
+    pub fn coasts_of(&self, province: &ProvinceKey) -> Vec<Coast> {
+        let mut coasts = self
+            .regions()
+            .filter(|r| r.province() == province)
+            .filter_map(|r| r.coast())
+            .collect::<Vec<_>>();
+        coasts.sort();
+        coasts
+    }
+
+    /// Get the regions for the other named coasts of `region`'s province, sorted in the order
+    /// north, east, south, west, such as `spa(sc)` for `spa(nc)`.
+    ///
+    /// For the coastless region of a province with named coasts, this is every coast of the
+    /// province. It is empty for provinces without named coasts.
+    pub fn sibling_coasts(&self, region: &RegionKey) -> Vec<RegionKey> {
+        self.coasts_of(region.province())
+            .into_iter()
+            .filter(|&coast| Some(coast) != region.coast())
+            .map(|coast| RegionKey::new(region.province().clone(), coast))
+            .collect()
+    }
+
+    /// Whether a fleet could move directly between two coasts of the same province.
+    ///
+    /// Coasts are only adjacent if the map has a border between them. The standard map has no
+    /// such borders, so a fleet can never move along the coastline within a province (DATC
+    /// 6.B.13).
+    pub fn coasts_adjacent(&self, province: &ProvinceKey, c1: Coast, c2: Coast) -> bool {
+        self.find_border_between(
+            &RegionKey::new(province.clone(), c1),
+            &RegionKey::new(province.clone(), c2),
+        )
+        .is_some()
+    }
+
+    /// Get the supply center provinces that a unit of type `unit_type` in `region` could reach
+    /// in a single move, sorted by name.
+    ///
+    /// If `include_convoys` is true, armies also report coastal supply centers they could reach
+    /// by a convoy through sea regions, assuming a fleet were present in each of them.
+    pub fn adjacent_supply_centers(
+        &self,
+        region: &RegionKey,
+        unit_type: UnitType,
+        include_convoys: bool,
+    ) -> Vec<&Province> {
+        let mut reachable = self
+            .borders_containing(region)
+            .into_iter()
+            .filter(|b| unit_type.can_occupy(b.terrain()))
+            .filter_map(|b| b.dest_from(region))
+            .map(RegionKey::province)
+            .collect::<HashSet<_>>();
+
+        if include_convoys && unit_type == UnitType::Army {
+            reachable.extend(self.convoy_reachable(region));
+        }
+
+        let mut provinces = reachable
+            .into_iter()
+            .filter_map(|p| self.provinces.get(&*p.short_name()))
+            .filter(|p| p.is_supply_center())
+            .collect::<Vec<_>>();
+        provinces.sort_by(|a, b| a.short_name.cmp(&b.short_name));
+        provinces
+    }
+
+    /// Whether a unit of type `unit_type` in `from` could only reach `to` by convoy: there is no
+    /// border into `to` that the unit can cross, but a chain of sea regions connects them.
+    ///
+    /// This only considers the map, not whether fleets are present to convoy the unit. Fleets
+    /// are never convoyed, so this is always false for them.
+    pub fn requires_convoy(&self, from: &RegionKey, to: &ProvinceKey, unit_type: UnitType) -> bool {
+        unit_type == UnitType::Army
+            && !self
+                .find_borders_between(from, to)
+                .into_iter()
+                .any(|b| unit_type.can_occupy(b.terrain()))
+            && self.convoy_reachable(from).contains(to)
+    }
+
+    /// The provinces an army in `region` could reach by a convoy through sea regions, assuming
+    /// a fleet were present in each of them.
+    fn convoy_reachable(&self, region: &RegionKey) -> HashSet<&ProvinceKey> {
+        let is_sea = |r: &RegionKey| {
+            self.find_region(&r.short_name())
+                .map(|r| r.terrain() == Terrain::Sea)
+                .unwrap_or(false)
+        };
+
+        let mut reachable = HashSet::default();
+        let mut seas = self
+            .find_bordering(region)
+            .into_iter()
+            .filter(|r| is_sea(r))
+            .collect::<Vec<_>>();
+        let mut visited = seas.iter().copied().collect::<HashSet<_>>();
+        while let Some(sea) = seas.pop() {
+            for next in self.find_bordering(sea) {
+                if is_sea(next) {
+                    if visited.insert(next) {
+                        seas.push(next);
+                    }
+                } else if next.province() != region.province() {
+                    reachable.insert(next.province());
+                }
+            }
+        }
+
+        reachable
+    }
+
+    /// Get the provinces which `nation` owns according to `ownerships`, sorted by name.
+    ///
+    /// Provinces in `ownerships` which are not in this map are ignored. See
+    /// [`supply_centers_owned_by`] to only get the supply centers.
+    pub fn provinces_owned_by(
+        &self,
+        ownerships: &HashMap<ProvinceKey, Nation>,
+        nation: &Nation,
+    ) -> Vec<&Province> {
+        let mut provinces = ownerships
+            .iter()
+            .filter(|(_, owner)| *owner == nation)
+            .filter_map(|(province, _)| self.provinces.get(&*province.short_name()))
+            .collect::<Vec<_>>();
+        provinces.sort_by(|a, b| a.short_name.cmp(&b.short_name));
+        provinces
+    }
+
+    /// Get the supply center ownerships at the start of a game: each nation owns its home
+    /// supply centers and all other supply centers are unowned.
+    pub fn initial_ownership(&self) -> HashMap<ProvinceKey, Nation> {
+        self.provinces()
+            .filter_map(|province| {
+                if let SupplyCenter::Home(nat) = &province.supply_center {
+                    Some((province.into(), nat.clone()))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Get the supply centers that no nation owns at the start of a game, sorted by name.
+    pub fn neutral_supply_centers(&self) -> Vec<&Province> {
+        let mut provinces = self
+            .provinces()
+            .filter(|p| p.supply_center == SupplyCenter::Neutral)
+            .collect::<Vec<_>>();
+        provinces.sort_by(|a, b| a.short_name.cmp(&b.short_name));
+        provinces
+    }
+
     /// Get all borders with a region.
     pub fn borders_containing<L: PartialEq<RegionKey>>(&self, r: &L) -> Vec<&Border> {
         self.borders.iter().filter(|b| b.contains(r)).collect()
@@ -56,6 +336,9 @@ impl Map {
         self.borders.iter().filter(|b| b.connects(r1, p2)).collect()
     }
 
+    /// Build an undirected graph with a node for each region and an edge for each border.
+    /// Requires the `std` feature.
+    #[cfg(feature = "std")]
     pub fn to_graph(&self) -> UnGraphMap<&Region, Terrain> {
         let mut graph = UnGraphMap::new();
         let node_index = self
@@ -74,15 +357,644 @@ impl Map {
 
         graph
     }
+
+    /// Check the map for likely authoring mistakes, such as duplicate borders or supply centers
+    /// that cannot be reached. Warnings are sorted so that the output is stable across calls.
+    pub fn validate(&self) -> Vec<MapWarning> {
+        let mut warnings = vec![];
+
+        let mut seen_borders = HashSet::default();
+        for border in &self.borders {
+            let (a, b) = border.sides();
+            let pair = if a <= b { (a, b) } else { (b, a) };
+            if !seen_borders.insert(pair) {
+                warnings.push(MapWarning::DuplicateBorder(pair.0.clone(), pair.1.clone()));
+            }
+        }
+
+        for region in self.regions() {
+            if region.coast().is_some()
+                && self
+                    .regions()
+                    .filter(|r| r.province() == region.province())
+                    .all(|r| r.terrain() == Terrain::Sea)
+            {
+                warnings.push(MapWarning::SeaProvinceWithCoast(region.into()));
+            }
+        }
+
+        for province in self.provinces().filter(|p| p.is_supply_center()) {
+            if !self.borders.iter().any(|b| {
+                let (a, b) = b.sides();
+                a.province() == province || b.province() == province
+            }) {
+                warnings.push(MapWarning::UnreachableSupplyCenter(province.into()));
+            }
+        }
+
+        warnings.sort();
+        warnings.dedup();
+        warnings
+    }
+
+    /// Render the map as a [Graphviz DOT](https://graphviz.org/doc/info/lang.html) graph,
+    /// with a node for each region and an edge for each border. Nodes and edges are colored
+    /// by terrain, and are sorted so that the output is stable across calls.
+    ///
+    /// This is intended for debugging custom maps, e.g. by piping the output through `dot -Tsvg`.
+    pub fn to_dot(&self) -> String {
+        let regions = self
+            .regions()
+            .map(|r| (RegionKey::from(r), r))
+            .collect::<HashMap<_, _>>();
+
+        let mut nodes = self.regions().collect::<Vec<_>>();
+        nodes.sort();
+
+        let mut edges = self
+            .borders
+            .iter()
+            .map(|border| {
+                let (a, b) = border.sides();
+                let a = regions[a];
+                let b = regions[b];
+                if a <= b {
+                    (a, b, border.terrain())
+                } else {
+                    (b, a, border.terrain())
+                }
+            })
+            .collect::<Vec<_>>();
+        edges.sort();
+        edges.dedup_by_key(|(a, b, _)| (*a, *b));
+
+        let mut dot = String::from("graph G {\n");
+        for region in nodes {
+            writeln!(
+                dot,
+                r#"  "{}" [color={}];"#,
+                region.short_name(),
+                terrain_color(region.terrain())
+            )
+            .expect("Writing to a string should not fail");
+        }
+
+        for (a, b, terrain) in edges {
+            writeln!(
+                dot,
+                r#"  "{}" -- "{}" [color={}];"#,
+                a.short_name(),
+                b.short_name(),
+                terrain_color(terrain)
+            )
+            .expect("Writing to a string should not fail");
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Write the map in the adjacency format read by
+    /// [`from_adjacency_str`](crate::geo::builder::from_adjacency_str), with one line
+    /// per region sorted by name and each region's neighbors sorted by name.
+    ///
+    /// The output is the same every time it is called. The map's name is not included.
+    pub fn to_adjacency(&self) -> String {
+        let mut regions = self.regions().collect::<Vec<_>>();
+        regions.sort();
+
+        let mut annotated = HashSet::default();
+        let mut text = String::new();
+        for region in regions {
+            let key = RegionKey::from(region);
+            write!(text, "{}\t{}", key, terrain_word(region.terrain()))
+                .expect("Writing to a string should not fail");
+
+            let province = &self.provinces[&*region.province().short_name()];
+            if annotated.insert(region.province()) {
+                match &province.supply_center {
+                    SupplyCenter::None => {}
+                    SupplyCenter::Neutral => text.push_str("\tsc=neutral"),
+                    SupplyCenter::Home(nation) => write!(text, "\tsc={}", nation)
+                        .expect("Writing to a string should not fail"),
+                }
+            }
+
+            let mut neighbors = self
+                .borders_containing(&key)
+                .into_iter()
+                .filter_map(|b| b.dest_from(&key).map(|dest| (dest, b.terrain())))
+                .collect::<Vec<_>>();
+            neighbors.sort();
+
+            for (index, (neighbor, terrain)) in neighbors.into_iter().enumerate() {
+                text.push(if index == 0 { '\t' } else { ' ' });
+
+                let neighbor_terrain = self
+                    .find_region(&neighbor.short_name())
+                    .map_or(terrain, Region::terrain);
+                if terrain == default_border_terrain(region.terrain(), neighbor_terrain) {
+                    write!(text, "{}", neighbor)
+                } else {
+                    write!(text, "{}:{}", neighbor, terrain_word(terrain))
+                }
+                .expect("Writing to a string should not fail");
+            }
+
+            text.push('\n');
+        }
+
+        text
+    }
+}
+
+/// A likely mistake in a map, found by [`Map::validate`].
+///
+/// Maps with warnings can still be used for adjudication, but may produce surprising results.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MapWarning {
+    /// More than one border was registered between the same pair of regions, possibly with
+    /// different terrain. Borders are undirected, so each adjacency should be registered once.
+    DuplicateBorder(RegionKey, RegionKey),
+    /// A region has a named coast, but its province has no land or coastal region for the
+    /// coast to belong to.
+    SeaProvinceWithCoast(RegionKey),
+    /// A supply center has no borders to any of its regions, so no unit can ever reach it.
+    UnreachableSupplyCenter(ProvinceKey),
+}
+
+/// The keyword for a terrain in the [adjacency format](Map::to_adjacency).
+fn terrain_word(terrain: Terrain) -> &'static str {
+    match terrain {
+        Terrain::Land => "land",
+        Terrain::Coast => "coast",
+        Terrain::Sea => "sea",
+    }
+}
+
+/// The Graphviz color used to draw regions and borders of a given terrain.
+fn terrain_color(terrain: Terrain) -> &'static str {
+    match terrain {
+        Terrain::Land => "darkgreen",
+        Terrain::Coast => "goldenrod",
+        Terrain::Sea => "blue",
+    }
+}
+
+/// The number of single-character insertions, deletions, and substitutions needed to turn
+/// `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != *cb);
+            current.push(substitution.min(prev[j + 1] + 1).min(current[j] + 1));
+        }
+        prev = current;
+    }
+
+    prev[b.len()]
 }
 
-impl From<BorderRegistry> for Map {
-    fn from(other: BorderRegistry) -> Self {
-        let (provinces, regions, borders) = other.contents();
-        Self {
+impl TryFrom<BorderRegistry> for Map {
+    type Error = MapError;
+
+    fn try_from(other: BorderRegistry) -> Result<Self, Self::Error> {
+        other.validate_home_supply_centers()?;
+        let (name, provinces, regions, borders) = other.contents();
+        Ok(Self {
+            name,
             provinces,
             regions,
             borders,
+        })
+    }
+}
+
+/// Get the supply centers which `nation` owns according to `ownerships`, sorted by name.
+///
+/// The number of these is the number of units the nation may have after a build phase.
+pub fn supply_centers_owned_by<'a>(
+    map: &'a Map,
+    ownerships: &HashMap<ProvinceKey, Nation>,
+    nation: &Nation,
+) -> Vec<&'a Province> {
+    map.provinces_owned_by(ownerships, nation)
+        .into_iter()
+        .filter(|p| p.is_supply_center())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MapWarning;
+    use crate::geo::builder::{MapError, ProvinceRegistry};
+    use crate::geo::{
+        standard_map, standard_map_with, Coast, Province, ProvinceKey, RegionKey,
+        StandardMapOptions, SupplyCenter, Terrain,
+    };
+    use crate::parser::ErrorKind;
+    use crate::{Nation, ShortName, UnitType};
+
+    #[test]
+    fn to_dot() {
+        let dot = standard_map().to_dot();
+        assert!(dot.starts_with("graph G {"));
+        assert!(dot.contains(r#"  "spa(nc)" [color=blue];"#));
+        assert!(dot.contains(r#"  "bur" -- "par" [color=darkgreen];"#));
+        assert_eq!(dot, standard_map().to_dot());
+    }
+
+    #[test]
+    fn adjacent_supply_centers() {
+        fn names(provinces: Vec<&Province>) -> Vec<&str> {
+            provinces
+                .into_iter()
+                .map(|p| p.short_name.as_str())
+                .collect()
+        }
+
+        let map = standard_map();
+
+        let ber = RegionKey::new("ber", None);
+        assert_eq!(
+            names(map.adjacent_supply_centers(&ber, UnitType::Army, false)),
+            vec!["kie", "mun"]
+        );
+        assert_eq!(
+            names(map.adjacent_supply_centers(&ber, UnitType::Fleet, false)),
+            vec!["kie"]
+        );
+
+        let convoyed = names(map.adjacent_supply_centers(&ber, UnitType::Army, true));
+        assert!(convoyed.contains(&"swe"));
+        assert!(convoyed.contains(&"den"));
+        assert!(!convoyed.contains(&"ber"));
+        assert!(!convoyed.contains(&"war"));
+    }
+
+    #[test]
+    fn suggest_regions() {
+        let map = standard_map();
+        let names = |short_name: &str, max: usize| {
+            map.suggest_regions(short_name, max)
+                .into_iter()
+                .map(|r| r.short_name().into_owned())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names("brr", 4), vec!["bar", "ber", "bre", "bur"]);
+        assert_eq!(names("Lon", 1), vec!["lon"]);
+        assert_eq!(names("stp(nc", 1), vec!["stp(nc)"]);
+        assert!(names("qqqqqq", 3).is_empty());
+    }
+
+    #[test]
+    fn provinces_owned_by() {
+        fn names(provinces: Vec<&Province>) -> Vec<&str> {
+            provinces
+                .into_iter()
+                .map(|p| p.short_name.as_str())
+                .collect()
+        }
+
+        let map = standard_map();
+        let mut ownerships = map.initial_ownership();
+        ownerships.insert("war".into(), Nation::from("GER"));
+        ownerships.insert("den".into(), Nation::from("GER"));
+        ownerships.insert("hol".into(), Nation::from("GER"));
+        ownerships.insert("sil".into(), Nation::from("GER"));
+        let germany = Nation::from("GER");
+
+        assert_eq!(
+            names(map.provinces_owned_by(&ownerships, &germany)),
+            vec!["ber", "den", "hol", "kie", "mun", "sil", "war"]
+        );
+        assert_eq!(
+            names(super::supply_centers_owned_by(map, &ownerships, &germany)),
+            vec!["ber", "den", "hol", "kie", "mun", "war"]
+        );
+        assert_eq!(
+            names(super::supply_centers_owned_by(
+                map,
+                &ownerships,
+                &Nation::from("RUS")
+            )),
+            vec!["mos", "sev", "stp"]
+        );
+    }
+
+    #[test]
+    fn initial_ownership() {
+        let map = standard_map();
+        let ownerships = map.initial_ownership();
+        assert_eq!(ownerships.len(), 22);
+        assert_eq!(
+            ownerships.get(&ProvinceKey::new("bud")),
+            Some(&Nation::from("AUS"))
+        );
+        assert_eq!(ownerships.get(&ProvinceKey::new("bel")), None);
+        assert_eq!(ownerships.get(&ProvinceKey::new("hol")), None);
+
+        let neutral = map
+            .neutral_supply_centers()
+            .into_iter()
+            .map(|p| p.short_name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(neutral.len(), 12);
+        assert!(neutral.contains(&"bel"));
+        assert!(neutral.contains(&"hol"));
+        assert!(!neutral.contains(&"bud"));
+    }
+
+    #[test]
+    fn coasts_of() {
+        let map = standard_map();
+        let bul = ProvinceKey::new("bul");
+        assert_eq!(map.coasts_of(&bul), vec![Coast::East, Coast::South]);
+        assert!(!map.coasts_adjacent(&bul, Coast::East, Coast::South));
+        assert!(map.coasts_of(&ProvinceKey::new("bre")).is_empty());
+        assert!(map.coasts_of(&ProvinceKey::new("xyz")).is_empty());
+    }
+
+    #[test]
+    fn sibling_coasts() {
+        let map = standard_map();
+        assert_eq!(
+            map.sibling_coasts(&RegionKey::new("spa", Coast::North)),
+            vec![RegionKey::new("spa", Coast::South)]
+        );
+        assert_eq!(
+            map.sibling_coasts(&RegionKey::new("spa", None)),
+            vec![
+                RegionKey::new("spa", Coast::North),
+                RegionKey::new("spa", Coast::South)
+            ]
+        );
+        assert!(map.sibling_coasts(&RegionKey::new("bre", None)).is_empty());
+    }
+
+    #[test]
+    fn standard_map_with_closed_black_sea() {
+        let map = standard_map_with(&StandardMapOptions {
+            closed_provinces: vec![ProvinceKey::new("bla")],
+            ..StandardMapOptions::default()
+        });
+
+        let sev = RegionKey::new("sev", None);
+        let ank = ProvinceKey::new("ank");
+        assert!(standard_map().requires_convoy(&sev, &ank, UnitType::Army));
+        assert!(!map.requires_convoy(&sev, &ank, UnitType::Army));
+        assert!(map
+            .find_bordering(&sev)
+            .iter()
+            .all(|r| r.short_name() != "bla"));
+        assert!(map.find_region("bla").is_none());
+        assert_eq!(map.name(), "");
+        assert!(map.validate().is_empty());
+    }
+
+    #[test]
+    fn standard_map_with_merged_coasts() {
+        let map = standard_map_with(&StandardMapOptions {
+            merge_split_coasts: true,
+            ..StandardMapOptions::default()
+        });
+
+        let spa = RegionKey::new("spa", None);
+        assert!(map.coasts_of(&ProvinceKey::new("spa")).is_empty());
+        assert_eq!(
+            map.find_region("spa").map(|r| r.terrain()),
+            Some(Terrain::Coast)
+        );
+        assert_eq!(
+            map.find_border_between(&spa, &RegionKey::new("por", None))
+                .map(|b| b.terrain()),
+            Some(Terrain::Coast)
+        );
+        assert_eq!(
+            map.find_border_between(&spa, &RegionKey::new("mao", None))
+                .map(|b| b.terrain()),
+            Some(Terrain::Sea)
+        );
+        assert!(map.validate().is_empty());
+    }
+
+    #[test]
+    fn standard_map_with_no_changes() {
+        assert_eq!(
+            &standard_map_with(&StandardMapOptions::default()),
+            standard_map()
+        );
+    }
+
+    #[test]
+    fn requires_convoy() {
+        let map = standard_map();
+        let lon = RegionKey::new("lon", None);
+        let bel = RegionKey::new("bel", None);
+        let hol = ProvinceKey::new("hol");
+        assert!(map.requires_convoy(&lon, &ProvinceKey::new("bel"), UnitType::Army));
+        assert!(!map.requires_convoy(&lon, &ProvinceKey::new("bel"), UnitType::Fleet));
+        assert!(!map.requires_convoy(&bel, &hol, UnitType::Army));
+        assert!(!map.requires_convoy(&lon, &ProvinceKey::new("mun"), UnitType::Army));
+    }
+
+    #[test]
+    fn region_key() {
+        let map = standard_map();
+        assert_eq!(map.region_key("Bel"), Some(RegionKey::new("bel", None)));
+        assert_eq!(
+            map.region_key("STP/NC"),
+            Some(RegionKey::new("stp", Coast::North))
+        );
+        assert_eq!(map.region_key("bel(nc)"), None);
+        assert_eq!(map.region_key("xyz"), None);
+    }
+
+    #[test]
+    fn resolve_region() {
+        let map = standard_map();
+        assert_eq!(
+            map.resolve_region("ber").map(RegionKey::from),
+            Ok(RegionKey::new("ber", None))
+        );
+
+        let error = map.resolve_region("brr").unwrap_err();
+        assert_eq!(
+            error.kind(),
+            &ErrorKind::UnknownRegion(vec!["bar".into(), "ber".into(), "bre".into()])
+        );
+        assert_eq!(
+            error.kind().to_string(),
+            "Unknown region; did you mean bar, ber, bre?"
+        );
+    }
+
+    #[test]
+    fn map_by_name() {
+        assert_eq!(standard_map().name(), "standard");
+        assert!(core::ptr::eq(
+            crate::geo::map_by_name("standard").unwrap(),
+            standard_map()
+        ));
+        assert!(crate::geo::map_by_name("1900").is_none());
+    }
+
+    #[test]
+    fn standard_nation_colors() {
+        let colors = crate::geo::standard_nation_colors();
+        assert_eq!(colors.len(), 7);
+        for position in crate::geo::standard_starting_positions() {
+            assert!(
+                colors.contains_key(position.nation()),
+                "{}",
+                position.nation()
+            );
+        }
+    }
+
+    #[test]
+    fn standard_starting_positions() {
+        let positions = crate::geo::standard_starting_positions();
+        assert_eq!(positions.len(), 22);
+
+        let count = |nation: &str| {
+            positions
+                .iter()
+                .filter(|p| *p.nation() == Nation::from(nation))
+                .count()
+        };
+        assert_eq!(count("RUS"), 4);
+        for nation in ["AUS", "ENG", "FRA", "GER", "ITA", "TUR"] {
+            assert_eq!(count(nation), 3, "{}", nation);
+        }
+
+        let map = standard_map();
+        for position in &positions {
+            let province = map
+                .provinces()
+                .find(|&p| *p == *position.region.province())
+                .unwrap();
+            assert_eq!(
+                province.supply_center,
+                SupplyCenter::Home(position.nation().clone()),
+                "{:?}",
+                position
+            );
+        }
+
+        let england = positions
+            .iter()
+            .filter(|p| *p.nation() == Nation::from("ENG"))
+            .map(|p| (p.unit.unit_type(), p.region.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            england,
+            vec![
+                (UnitType::Fleet, "edi".to_string()),
+                (UnitType::Fleet, "lon".to_string()),
+                (UnitType::Army, "lvp".to_string()),
+            ]
+        );
+        assert!(positions
+            .iter()
+            .any(|p| p.region == RegionKey::new("stp", Coast::South)));
+    }
+
+    #[test]
+    fn sole_region() {
+        let map = standard_map();
+        assert_eq!(map.sole_region(&ProvinceKey::from("spa")), None);
+        assert_eq!(
+            map.sole_region(&ProvinceKey::from("par"))
+                .map(RegionKey::from),
+            Some(RegionKey::new("par", None))
+        );
+        assert_eq!(map.sole_region(&ProvinceKey::from("xyz")), None);
+    }
+
+    #[test]
+    fn validate_standard_map() {
+        assert_eq!(standard_map().validate(), vec![]);
+    }
+
+    #[test]
+    fn validate_finds_mistakes() {
+        let mut provinces = ProvinceRegistry::default();
+        for (name, supply_center) in [
+            ("abc", SupplyCenter::Neutral),
+            ("def", SupplyCenter::None),
+            ("ghi", SupplyCenter::None),
+            ("xyz", SupplyCenter::Neutral),
+        ] {
+            provinces
+                .register(Province {
+                    short_name: name.into(),
+                    supply_center,
+                })
+                .unwrap();
         }
+
+        let mut regions = provinces.finish();
+        regions.register("abc", None, Terrain::Coast).unwrap();
+        regions.register("def", None, Terrain::Sea).unwrap();
+        regions.register("ghi", Coast::North, Terrain::Sea).unwrap();
+        regions.register("xyz", None, Terrain::Land).unwrap();
+
+        let mut borders = regions.finish();
+        borders.register("abc", "def", Terrain::Sea).unwrap();
+        borders.register("def", "abc", Terrain::Sea).unwrap();
+        borders.register("def", "ghi(nc)", Terrain::Sea).unwrap();
+
+        let (_, warnings) = borders.finish_with_warnings().unwrap();
+        assert_eq!(
+            warnings,
+            vec![
+                MapWarning::DuplicateBorder(
+                    RegionKey::new("abc", None),
+                    RegionKey::new("def", None)
+                ),
+                MapWarning::SeaProvinceWithCoast(RegionKey::new("ghi", Coast::North)),
+                MapWarning::UnreachableSupplyCenter(ProvinceKey::from("xyz")),
+            ]
+        );
+    }
+
+    #[test]
+    fn home_supply_center_without_region_is_rejected() {
+        let mut provinces = ProvinceRegistry::default();
+        for (name, supply_center) in [
+            ("abc", SupplyCenter::Home(Nation::from("FRA"))),
+            ("def", SupplyCenter::None),
+        ] {
+            provinces
+                .register(Province {
+                    short_name: name.into(),
+                    supply_center,
+                })
+                .unwrap();
+        }
+
+        // The home supply center was declared, but its region was forgotten.
+        let mut regions = provinces.finish();
+        regions.register("def", None, Terrain::Land).unwrap();
+
+        let err = regions.finish().finish().unwrap_err();
+        assert!(matches!(err, MapError::UnoccupiableHomeSupplyCenter(p) if p == "abc"));
+    }
+
+    #[test]
+    fn provinces_by_terrain() {
+        let map = standard_map();
+        assert_eq!(map.sea_provinces().count(), 19);
+        // Switzerland is impassable, but is still a land province.
+        assert_eq!(map.land_provinces().count(), 15);
+        assert_eq!(map.coastal_provinces().count(), 42);
+        assert!(map.coastal_provinces().any(|p| p.short_name == "spa"));
+        assert!(map.coastal_provinces().any(|p| p.short_name == "bre"));
+        assert!(map.land_provinces().any(|p| p.short_name == "par"));
     }
 }
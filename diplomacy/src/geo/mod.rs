@@ -12,7 +12,12 @@ pub mod builder;
 
 pub use self::border::Border;
 pub use self::location::Location;
-pub use self::map::Map;
+pub use self::map::{supply_centers_owned_by, Map, MapWarning};
 pub use self::province::{Province, ProvinceKey, SupplyCenter};
 pub use self::region::{Coast, Region, RegionKey, Terrain};
-pub use self::standard::standard_map;
+pub use self::standard::{
+    build_standard_map, standard_map_with, standard_nation_colors, standard_starting_positions,
+    StandardMapOptions,
+};
+#[cfg(feature = "std")]
+pub use self::standard::{map_by_name, standard_map};
@@ -1,6 +1,7 @@
 use crate::Nation;
 use crate::ShortName;
-use std::borrow::Cow;
+use alloc::borrow::Cow;
+use alloc::string::String;
 
 /// The supply-center nature of a province. This information is used in the build phase
 /// to determine how many units a nation can sustain and where new units can be built.
@@ -42,6 +43,9 @@ impl PartialEq<ProvinceKey> for Province {
 }
 
 /// An identifier that can be resolved to a province
+///
+/// Keys are ordered by their short names. Sorted collections emitted by the crate rely on
+/// this order, so it will not change between releases.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ProvinceKey(String);
@@ -52,7 +56,7 @@ impl ProvinceKey {
     }
 }
 
-impl<'a> From<&'a Province> for ProvinceKey {
+impl From<&Province> for ProvinceKey {
     fn from(p: &Province) -> Self {
         ProvinceKey(p.short_name().into_owned())
     }
@@ -64,7 +68,7 @@ impl From<String> for ProvinceKey {
     }
 }
 
-impl<'a> From<&'a str> for ProvinceKey {
+impl From<&str> for ProvinceKey {
     fn from(s: &str) -> Self {
         ProvinceKey(String::from(s))
     }
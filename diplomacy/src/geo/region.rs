@@ -1,9 +1,10 @@
 use crate::geo::{Location, ProvinceKey};
 use crate::parser::{Error, ErrorKind};
 use crate::ShortName;
-use std::borrow::Cow;
-use std::fmt;
-use std::str::FromStr;
+use alloc::borrow::Cow;
+use alloc::{format, string::String, vec::Vec};
+use core::fmt;
+use core::str::FromStr;
 
 /// Differentiates regions within a province.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -105,6 +106,10 @@ impl Location for Region {
 }
 
 /// An identifier that references a region.
+///
+/// Keys are ordered by province, then by coast, with the coastless region first and coasts
+/// in the order north, east, south, west. Sorted collections emitted by the crate rely on
+/// this order, so it will not change between releases.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RegionKey(
@@ -177,9 +182,21 @@ impl ShortName for RegionKey {
     }
 }
 
+/// Regions are displayed using their short name, e.g. `spa(nc)`.
+///
+/// The alternate flag (`{:#}`) instead writes coasts in the slash notation used by
+/// some other tools, e.g. `spa/nc`.
 impl fmt::Display for RegionKey {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.short_name())
+        match self.coast() {
+            Some(coast) if f.alternate() => write!(
+                f,
+                "{}/{}",
+                self.province().short_name(),
+                coast.short_name().trim_matches(|c| c == '(' || c == ')')
+            ),
+            _ => write!(f, "{}", self.short_name()),
+        }
     }
 }
 
@@ -191,10 +208,24 @@ impl Location for RegionKey {
     }
 }
 
+/// Parse a region key from its short name, e.g. `spa(nc)`.
+///
+/// The slash notation used by some other tools, e.g. `spa/nc`, is also accepted.
 impl FromStr for RegionKey {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((province, coast_id)) = s.split_once('/') {
+            return if province.is_empty() || coast_id.contains('/') {
+                Err(Error::new(ErrorKind::MalformedRegion, s))
+            } else {
+                Ok(RegionKey::new(
+                    String::from(province),
+                    Coast::from_str(coast_id)?,
+                ))
+            };
+        }
+
         let parts = s.split('(').collect::<Vec<_>>();
 
         // No parentheses means no coast.
@@ -215,7 +246,7 @@ impl FromStr for RegionKey {
 
 #[cfg(test)]
 mod test {
-    use std::str::FromStr;
+    use core::str::FromStr;
 
     use super::{Coast, RegionKey};
     use crate::parser::ErrorKind;
@@ -237,4 +268,31 @@ mod test {
             RegionKey::from_str("foo(bar)").unwrap_err().kind()
         );
     }
+
+    #[test]
+    fn parse_region_slash_notation() {
+        let spa_nc = RegionKey::new("spa", Coast::North);
+        assert_eq!(spa_nc, RegionKey::from_str("spa/nc").unwrap());
+        assert_eq!(spa_nc, RegionKey::from_str("spa(nc)").unwrap());
+        assert_eq!(
+            &ErrorKind::BadCoast,
+            RegionKey::from_str("spa/xc").unwrap_err().kind()
+        );
+        assert_eq!(
+            &ErrorKind::MalformedRegion,
+            RegionKey::from_str("spa/nc/sc").unwrap_err().kind()
+        );
+    }
+
+    #[test]
+    fn display_region() {
+        let spa_nc = RegionKey::new("spa", Coast::North);
+        assert_eq!("spa(nc)", spa_nc.to_string());
+        assert_eq!("spa/nc", format!("{:#}", spa_nc));
+        assert_eq!("aeg", format!("{:#}", RegionKey::new("aeg", None)));
+
+        for notation in [spa_nc.to_string(), format!("{:#}", spa_nc)] {
+            assert_eq!(spa_nc, RegionKey::from_str(&notation).unwrap());
+        }
+    }
 }
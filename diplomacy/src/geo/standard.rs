@@ -1,26 +1,136 @@
+use crate::collections::HashMap;
 use crate::geo::builder::ProvinceRegistry;
-use crate::geo::{Coast, Map, Province, SupplyCenter, Terrain};
+use crate::geo::{Coast, Map, Province, ProvinceKey, RegionKey, SupplyCenter, Terrain};
+use crate::ShortName;
+use crate::{Nation, Unit, UnitPosition, UnitType};
+use alloc::borrow::Cow;
+use alloc::{string::String, vec::Vec};
+use core::str::FromStr;
+#[cfg(feature = "std")]
 use lazy_static::lazy_static;
 
+#[cfg(feature = "std")]
 lazy_static! {
-    static ref STANDARD_MAP: Map = load_standard();
+    static ref STANDARD_MAP: Map = build_standard_map();
 }
 
+/// The name of the standard map.
+const STANDARD_NAME: &str = "standard";
+
 /// Gets a static reference to the standard game world map.
 /// See [this SVG](https://upload.wikimedia.org/wikipedia/commons/a/a3/Diplomacy.svg)
 /// for the source names and borders.
+///
+/// The map is named `"standard"`. Requires the `std` feature.
+#[cfg(feature = "std")]
 pub fn standard_map() -> &'static Map {
     &STANDARD_MAP
 }
 
-fn load_standard() -> Map {
+/// Gets a static reference to a built-in map by its [name](Map::name), or `None` if no
+/// built-in map has that name.
+///
+/// This allows stored data to refer to a map by name rather than embedding the map.
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+pub fn map_by_name(name: &str) -> Option<&'static Map> {
+    match name {
+        STANDARD_NAME => Some(standard_map()),
+        _ => None,
+    }
+}
+
+/// The units on the board at the start of a standard game, in Spring 1901, sorted by nation.
+///
+/// At the start of the game each nation owns its home supply centers; see
+/// [`to_initial_ownerships`](crate::judge::build::to_initial_ownerships).
+///
+/// ```
+/// use diplomacy::geo::{standard_map, standard_starting_positions};
+/// use diplomacy::judge::{MappedMainOrder, Rulebook, Submission};
+/// use diplomacy::StartingPositions;
+///
+/// let start = StartingPositions(standard_starting_positions());
+/// let orders = vec!["FRA: A par -> bur".parse::<MappedMainOrder>().unwrap()];
+/// let submission = Submission::new(standard_map(), &start, orders);
+/// let outcome = submission.adjudicate(Rulebook::default());
+/// assert_eq!(outcome.all_orders().count(), 22);
+/// ```
+pub fn standard_starting_positions() -> Vec<UnitPosition<'static, RegionKey>> {
+    include_str!("starting_units.csv")
+        .lines()
+        .skip(1)
+        .map(|line| {
+            unit_position_from_line(line)
+                .unwrap_or_else(|_| panic!("Failed reading starting unit: {}", line))
+        })
+        .collect()
+}
+
+/// The color each power in a standard game is drawn in, as RGB, so that every client shows
+/// a nation the same way.
+///
+/// The colors follow the usual board: England is blue, France cyan, Germany brown, Italy
+/// green, Austria red, Russia purple, and Turkey yellow.
+pub fn standard_nation_colors() -> HashMap<Nation, [u8; 3]> {
+    [
+        ("AUS", [200, 40, 40]),
+        ("ENG", [30, 60, 170]),
+        ("FRA", [60, 190, 230]),
+        ("GER", [120, 80, 40]),
+        ("ITA", [40, 150, 60]),
+        ("RUS", [130, 80, 170]),
+        ("TUR", [230, 190, 40]),
+    ]
+    .into_iter()
+    .map(|(nation, color)| (Nation::from(nation), color))
+    .collect()
+}
+
+/// Builds a new copy of the standard game world map.
+///
+/// Prefer [`standard_map`] where available; this is for `no_std` targets, which have no way
+/// to lazily initialize the shared map.
+pub fn build_standard_map() -> Map {
+    standard_map_with(&StandardMapOptions::default())
+}
+
+/// Changes to make to the standard map, for use with [`standard_map_with`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct StandardMapOptions {
+    /// Provinces to remove from the map along with their borders, such as `bla` for a puzzle
+    /// in which the Black Sea is impassable.
+    pub closed_provinces: Vec<ProvinceKey>,
+    /// Whether to merge the named coasts of Bulgaria, Spain, and St Petersburg into their
+    /// provinces, so that those provinces are ordinary coastal provinces whose fleets can
+    /// reach every sea bordering them.
+    ///
+    /// The standard starting fleet in `stp(sc)` is then in `stp`.
+    pub merge_split_coasts: bool,
+}
+
+/// Builds a copy of the standard map with the changes described by `options`.
+///
+/// The map is named `"standard"` only if no changes are made, so that a modified map is
+/// never mistaken for the standard map by [`map_by_name`](crate::geo::map_by_name).
+pub fn standard_map_with(options: &StandardMapOptions) -> Map {
+    let is_open = |region: &str| {
+        !options
+            .closed_provinces
+            .iter()
+            .any(|closed| *closed == RegionKey::from_str(region).unwrap())
+    };
+
     let mut prov_reg = ProvinceRegistry::default();
     let provinces = include_str!("provinces.csv").lines().skip(1);
     for line in provinces {
         if let Ok(prov) = province_from_line(line) {
-            prov_reg
-                .register(prov)
-                .expect("standard map shouldn't have issues");
+            if is_open(&prov.short_name) {
+                prov_reg
+                    .register(prov)
+                    .expect("standard map shouldn't have issues");
+            }
         } else {
             panic!("Failed registering province: {}", line)
         }
@@ -30,22 +140,75 @@ fn load_standard() -> Map {
     let regions = include_str!("regions.csv").lines().skip(1);
     for line in regions {
         if let Ok((prov, coast, terrain)) = region_from_line(line) {
-            region_reg.register(prov, coast, terrain).unwrap();
+            if !is_open(prov) {
+                continue;
+            }
+
+            if options.merge_split_coasts && SPLIT_COAST_PROVINCES.contains(&prov) {
+                if coast.is_none() {
+                    region_reg.register(prov, None, Terrain::Coast).unwrap();
+                }
+            } else {
+                region_reg.register(prov, coast, terrain).unwrap();
+            }
         } else {
             panic!("Failed registering region: {}", line)
         }
     }
 
-    let mut border_reg = region_reg.finish();
-    let borders = include_str!("borders.csv").lines().skip(1);
-    for line in borders {
+    let mut borders = Vec::<(String, String, Terrain)>::new();
+    for line in include_str!("borders.csv").lines().skip(1) {
         let words = line.split(',').collect::<Vec<_>>();
-        border_reg
-            .register(words[0], words[1], terrain_from_word(words[2]).unwrap())
-            .unwrap();
+        if !is_open(words[0]) || !is_open(words[1]) {
+            continue;
+        }
+
+        let terrain = terrain_from_word(words[2]).unwrap();
+        if !options.merge_split_coasts {
+            borders.push((words[0].into(), words[1].into(), terrain));
+            continue;
+        }
+
+        // Merging coasts can give a pair of regions both a land and a sea border, which
+        // together make a single coastal border.
+        let (r1, r2) = (merged_region(words[0]), merged_region(words[1]));
+        if let Some(existing) = borders
+            .iter_mut()
+            .find(|(b1, b2, _)| (*b1 == r1 && *b2 == r2) || (*b1 == r2 && *b2 == r1))
+        {
+            if existing.2 != terrain {
+                existing.2 = Terrain::Coast;
+            }
+        } else {
+            borders.push((r1, r2, terrain));
+        }
+    }
+
+    let mut border_reg = region_reg.finish();
+    if *options == StandardMapOptions::default() {
+        border_reg.set_name(STANDARD_NAME);
     }
 
-    border_reg.finish()
+    for (r1, r2, terrain) in borders {
+        border_reg.register(&r1, &r2, terrain).unwrap();
+    }
+
+    border_reg
+        .finish()
+        .expect("standard map shouldn't have issues")
+}
+
+/// The provinces of the standard map with named coasts.
+const SPLIT_COAST_PROVINCES: [&str; 3] = ["bul", "spa", "stp"];
+
+/// The region a border endpoint refers to once split coasts are merged into their provinces.
+fn merged_region(region: &str) -> String {
+    let key = RegionKey::from_str(region).unwrap();
+    if SPLIT_COAST_PROVINCES.contains(&&*key.province().short_name()) {
+        key.province().short_name().into_owned()
+    } else {
+        region.into()
+    }
 }
 
 fn province_from_line(s: &str) -> Result<Province, ()> {
@@ -60,7 +223,7 @@ fn province_from_line(s: &str) -> Result<Province, ()> {
     }
 }
 
-fn supply_center_from_word(s: &str) -> SupplyCenter {
+pub(super) fn supply_center_from_word(s: &str) -> SupplyCenter {
     match s {
         "" => SupplyCenter::None,
         "neutral" => SupplyCenter::Neutral,
@@ -68,6 +231,19 @@ fn supply_center_from_word(s: &str) -> SupplyCenter {
     }
 }
 
+fn unit_position_from_line(s: &str) -> Result<UnitPosition<'static, RegionKey>, ()> {
+    let words = s.split(',').collect::<Vec<_>>();
+    if words.len() == 4 {
+        let unit_type = words[1].parse::<UnitType>().map_err(|_| ())?;
+        Ok(UnitPosition::new(
+            Unit::new(Cow::Owned(Nation::from(words[0])), unit_type),
+            RegionKey::new(words[2], coast_from_word(words[3])?),
+        ))
+    } else {
+        Err(())
+    }
+}
+
 fn region_from_line(s: &str) -> Result<(&str, Option<Coast>, Terrain), ()> {
     let words = s.split(',').collect::<Vec<_>>();
     if words.len() == 3 {
@@ -92,7 +268,7 @@ fn coast_from_word(w: &str) -> Result<Option<Coast>, ()> {
     }
 }
 
-fn terrain_from_word(w: &str) -> Result<Terrain, ()> {
+pub(super) fn terrain_from_word(w: &str) -> Result<Terrain, ()> {
     match w {
         "sea" => Ok(Terrain::Sea),
         "coast" => Ok(Terrain::Coast),
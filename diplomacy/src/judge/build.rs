@@ -1,11 +1,15 @@
 //! Resolver for build phases.
 
-use super::{MappedBuildOrder, OrderState};
+use super::{retreat, MappedBuildOrder, OrderState, PhaseOutcome, Rulebook};
+use crate::collections::{HashMap, HashSet};
 use crate::geo::{Map, ProvinceKey, RegionKey, SupplyCenter};
 use crate::order::BuildCommand;
-use crate::{Nation, ShortName, UnitType};
-use std::collections::{HashMap, HashSet};
-use std::convert::TryInto;
+use crate::{Nation, ShortName, Unit, UnitPosition, UnitPositions, UnitType};
+use alloc::borrow::Cow;
+use alloc::{string::ToString, vec, vec::Vec};
+use core::cmp::Ordering;
+use core::convert::TryInto;
+use core::fmt;
 
 /// The outcome of a build-turn order.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -20,8 +24,8 @@ pub enum OrderOutcome {
     RedeploymentProhibited,
     /// The build command was to a province where the issuing nation cannot build.
     InvalidProvince,
-    /// The build command was to a home SC for the issuing power, but another
-    /// power currently controls it.
+    /// The build command was to a province where the issuing power could build,
+    /// but another power currently controls it.
     ForeignControlled,
     /// Build failed because the target province already has a friendly unit in it.
     OccupiedProvince,
@@ -47,12 +51,35 @@ impl From<OrderOutcome> for OrderState {
     }
 }
 
+/// A concise description for logs, such as `Fails: province occupied`.
+impl fmt::Display for OrderOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use OrderOutcome::*;
+        match self {
+            Succeeds => write!(f, "Succeeds"),
+            RedeploymentProhibited => write!(f, "Fails: cannot build and disband in one turn"),
+            InvalidProvince => write!(f, "Fails: cannot build in province"),
+            ForeignControlled => write!(f, "Fails: controlled by another nation"),
+            OccupiedProvince => write!(f, "Fails: province occupied"),
+            InvalidTerrain => write!(f, "Fails: unit cannot occupy region"),
+            DisbandingNonexistentUnit => write!(f, "Fails: no unit to disband"),
+            DisbandingForeignUnit => write!(f, "Fails: foreign unit"),
+            AllBuildsUsed => write!(f, "Fails: no builds left"),
+            AllDisbandsUsed => write!(f, "Fails: no disbands left"),
+        }
+    }
+}
+
 /// Provider for the resolver to get state about the game world that it needs to successfully
 /// judge a build phase.
 pub trait WorldState {
     /// Get the set of nations in the game. This must include nations that issued no
     /// orders this turn, and may include nations that have no units if those units
     /// are entitled to build.
+    ///
+    /// Every nation with units must be included, even if it owns no supply centers. Such a
+    /// nation has been eliminated: all its units are disbanded in civil disorder and its
+    /// build orders fail. Once it has no units it may be left out of later turns.
     fn nations(&self) -> HashSet<&Nation>;
     /// Get the nation with a unit _currently in_ the specified province. This should
     /// return `None` if the province is vacant, even if it's controlled by a nation.
@@ -63,9 +90,93 @@ pub trait WorldState {
     fn units(&self, nation: &Nation) -> HashSet<(UnitType, RegionKey)>;
 }
 
+/// Rules for the parts of build-phase adjudication that vary between editions and variants.
+pub trait Adjudicate {
+    /// Compare two units of a nation that did not issue enough disband orders. Units which
+    /// sort first are disbanded first.
+    ///
+    /// Implementations must provide a total order, or the set of disbanded units will not
+    /// be deterministic.
+    fn disband_priority(&self, a: &DisbandCandidate<'_>, b: &DisbandCandidate<'_>) -> Ordering;
+
+    /// Where nations are allowed to build new units.
+    fn build_policy(&self) -> BuildPolicy {
+        BuildPolicy::HomeOnly
+    }
+
+    /// Which supply centers are used to measure [`DisbandCandidate::distance`].
+    fn disband_distance(&self) -> DisbandDistance {
+        DisbandDistance::default()
+    }
+}
+
+impl Adjudicate for Rulebook {
+    /// Per the DATC, units are sorted by distance from an owned SC. Equidistant fleets
+    /// are disbanded before armies, and sorting of units within the same type is done
+    /// alphabetically.
+    fn disband_priority(&self, a: &DisbandCandidate<'_>, b: &DisbandCandidate<'_>) -> Ordering {
+        // Distance from nearest owned supply center, descending
+        b.distance
+            .cmp(&a.distance)
+            // when equidistant, disband fleets before armies
+            .then(b.unit_type.cmp(&a.unit_type))
+            // when units are same type and equidistant, disband in alphabetical order
+            .then_with(|| a.region.cmp(&b.region))
+    }
+
+    fn build_policy(&self) -> BuildPolicy {
+        self.build_policy
+    }
+
+    fn disband_distance(&self) -> DisbandDistance {
+        self.disband_distance
+    }
+}
+
+/// The set of provinces in which a nation may build new units.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BuildPolicy {
+    /// Nations may only build in their own home supply centers, as in the standard game.
+    #[default]
+    HomeOnly,
+    /// Nations may build in any supply center they control, as in variants such as
+    /// "Chaos" and "Build Anywhere".
+    AnyOwnedSc,
+}
+
+/// The supply centers a unit's distance is measured from when choosing which units to
+/// disband in civil disorder.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DisbandDistance {
+    /// Measure distance to the nearest supply center the nation owns, per the 2023 rulebook
+    /// and the current DATC.
+    #[default]
+    OwnedSc,
+    /// Measure distance to the nearest of the nation's home supply centers, whether or not
+    /// the nation still owns it, as in the 1971 and 1982 rulebooks.
+    HomeSc,
+}
+
+/// A unit which may be disbanded because its nation did not issue enough disband orders
+/// to cover their supply center losses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisbandCandidate<'a> {
+    /// The nation that owns the unit.
+    pub nation: &'a Nation,
+    pub unit_type: UnitType,
+    pub region: RegionKey,
+    /// The number of steps between the unit and the nearest supply center its nation owns
+    /// (or, under [`DisbandDistance::HomeSc`], its nearest home supply center), ignoring
+    /// terrain. This is `u32::MAX` if no such supply center can be reached.
+    pub distance: u32,
+}
+
 /// The immutable pieces of a build-phase order resolution
-pub struct Context<'a, W: WorldState> {
+pub struct Context<'a, W: WorldState, A = Rulebook> {
     world: &'a Map,
+    rules: A,
     home_scs: HashMap<&'a Nation, HashSet<ProvinceKey>>,
     ownerships: HashMap<&'a Nation, HashSet<ProvinceKey>>,
     last_time: &'a HashMap<ProvinceKey, Nation>,
@@ -74,7 +185,7 @@ pub struct Context<'a, W: WorldState> {
 }
 
 impl<'a, W: WorldState> Context<'a, W> {
-    /// Create a new context for resolution.
+    /// Create a new context for resolution using the standard rules.
     ///
     /// # First Winter
     /// The first build phase of the game should pass the initial supply center ownerships to
@@ -90,15 +201,15 @@ impl<'a, W: WorldState> Context<'a, W> {
             panic!("At least one supply center must have been owned by at least one nation. Did you forget to pass the initial world state?");
         }
 
-        let mut home_scs = HashMap::with_capacity(25);
-        let mut ownerships = HashMap::<&Nation, HashSet<ProvinceKey>>::new();
+        let mut home_scs = HashMap::with_capacity_and_hasher(25, Default::default());
+        let mut ownerships = HashMap::<&Nation, HashSet<ProvinceKey>>::default();
 
         // Figure out who owns what and where nations are allowed to build.
         for province in world.provinces().filter(|p| p.is_supply_center()) {
             if let SupplyCenter::Home(nat) = &province.supply_center {
                 home_scs
                     .entry(nat)
-                    .or_insert_with(HashSet::new)
+                    .or_insert_with(HashSet::default)
                     .insert(province.into());
             }
 
@@ -110,6 +221,7 @@ impl<'a, W: WorldState> Context<'a, W> {
 
         Self {
             world,
+            rules: Rulebook::default(),
             home_scs,
             ownerships,
             last_time,
@@ -117,6 +229,39 @@ impl<'a, W: WorldState> Context<'a, W> {
             orders: orders.into_iter().collect(),
         }
     }
+}
+
+impl<'a, 'b> Context<'a, retreat::Outcome<'b>> {
+    /// Create a new context for the build phase that follows a retreat phase, using the
+    /// standard rules.
+    ///
+    /// The units left after `post_retreat` are the world state, so supply centers are owned
+    /// by whoever occupies them at the end of the retreat phase, or otherwise by their owner
+    /// in `last_time`. If the fall turn dislodged no units, resolve its retreat phase without
+    /// orders to get an outcome to pass here.
+    pub fn from_previous(
+        world: &'a Map,
+        last_time: &'a HashMap<ProvinceKey, Nation>,
+        post_retreat: &'a retreat::Outcome<'b>,
+        orders: impl IntoIterator<Item = MappedBuildOrder>,
+    ) -> Self {
+        Self::new(world, last_time, post_retreat, orders)
+    }
+}
+
+impl<'a, W: WorldState, A: Adjudicate> Context<'a, W, A> {
+    /// Replace the rules used to resolve the build phase.
+    pub fn with_rules<R: Adjudicate>(self, rules: R) -> Context<'a, W, R> {
+        Context {
+            world: self.world,
+            rules,
+            home_scs: self.home_scs,
+            ownerships: self.ownerships,
+            last_time: self.last_time,
+            this_time: self.this_time,
+            orders: self.orders,
+        }
+    }
 
     pub fn current_owner(&'a self, province: &ProvinceKey) -> Option<&'a Nation> {
         self.this_time
@@ -124,6 +269,21 @@ impl<'a, W: WorldState> Context<'a, W> {
             .or_else(|| self.last_time.get(province))
     }
 
+    /// The supply center ownerships at the end of this build phase.
+    ///
+    /// Pass this as `last_time` to the next build phase, along with the previous phase's
+    /// [`Outcome`] as its world state if no units moved in between.
+    pub fn to_ownerships(&self) -> HashMap<ProvinceKey, Nation> {
+        self.ownerships
+            .iter()
+            .flat_map(|(&nation, provinces)| {
+                provinces
+                    .iter()
+                    .map(move |province| (province.clone(), nation.clone()))
+            })
+            .collect()
+    }
+
     pub fn resolve(&'a self) -> Outcome<'a> {
         Resolution::new(self).resolve(self)
     }
@@ -137,7 +297,7 @@ struct Resolution<'a> {
 }
 
 impl<'a> Resolution<'a> {
-    pub fn new<W: WorldState>(context: &'a Context<W>) -> Self {
+    pub fn new<W: WorldState, A: Adjudicate>(context: &'a Context<W, A>) -> Self {
         let final_units = context
             .this_time
             .nations()
@@ -145,12 +305,17 @@ impl<'a> Resolution<'a> {
             .map(|nation| (nation, context.this_time.units(nation)))
             .collect();
 
+        // Nations that own no supply centers are only found through the world state, and
+        // must still disband all their units.
         let deltas = context
             .ownerships
-            .iter()
-            .filter_map(|(&nation, ownerships)| {
-                let adjustment = i16::from(ownerships.len() as u8)
-                    - i16::from(context.this_time.unit_count(nation));
+            .keys()
+            .copied()
+            .chain(context.this_time.nations())
+            .filter_map(|nation| {
+                let owned = context.ownerships.get(nation).map_or(0, HashSet::len);
+                let adjustment =
+                    i16::from(owned as u8) - i16::from(context.this_time.unit_count(nation));
                 match adjustment {
                     0 => None,
                     x if x > 0 => Some((nation, (BuildCommand::Build, x))),
@@ -161,13 +326,16 @@ impl<'a> Resolution<'a> {
 
         Resolution {
             deltas,
-            state: HashMap::with_capacity(context.orders.len()),
-            civil_disorder: HashSet::new(),
+            state: HashMap::with_capacity_and_hasher(context.orders.len(), Default::default()),
+            civil_disorder: HashSet::default(),
             final_units,
         }
     }
 
-    pub fn resolve(mut self, context: &'a Context<impl WorldState>) -> Outcome<'a> {
+    pub fn resolve(
+        mut self,
+        context: &'a Context<impl WorldState, impl Adjudicate>,
+    ) -> Outcome<'a> {
         for order in &context.orders {
             self.resolve_order(context, order);
         }
@@ -183,7 +351,7 @@ impl<'a> Resolution<'a> {
 
     fn resolve_order(
         &mut self,
-        context: &'a Context<impl WorldState>,
+        context: &'a Context<impl WorldState, impl Adjudicate>,
         order: &'a MappedBuildOrder,
     ) -> OrderOutcome {
         use self::OrderOutcome::*;
@@ -252,9 +420,13 @@ impl<'a> Resolution<'a> {
     /// Balance unit populations with national supply centers by forcibly disbanding
     /// excess units. This will only have an effect if a nation did not issue enough
     /// disband orders to cover their supply center losses.
-    fn compute_mandatory_disbands(&mut self, context: &'a Context<impl WorldState>) {
-        let world_graph = context.world.to_graph();
-
+    ///
+    /// Disbands the nation did order have already been removed from `final_units` and
+    /// deducted from its delta, so only the shortfall is chosen here.
+    fn compute_mandatory_disbands(
+        &mut self,
+        context: &'a Context<impl WorldState, impl Adjudicate>,
+    ) {
         for (nation, delta) in &mut self.deltas {
             if delta.0 == BuildCommand::Build || delta.1 == 0 {
                 continue;
@@ -263,82 +435,70 @@ impl<'a> Resolution<'a> {
             let usize_delta: usize = delta.1.try_into().unwrap();
             let units = self.final_units.remove(nation).unwrap();
 
-            // Per 2023 rulebook, units disband based on distance from the nation's owned
-            // supply centers (earlier editions had it based on distance from the home supply centers)
             let Some(owned_scs) = context.ownerships.get(*nation) else {
                 // If there are no owned supply centers, all units disband
                 self.civil_disorder.extend(units);
                 continue;
             };
 
-            // Get all regions in the owned supply centers. The rules require checking
+            // Per 2023 rulebook, units disband based on distance from the nation's owned
+            // supply centers (earlier editions had it based on distance from the home supply centers)
+            let distance_scs = match context.rules.disband_distance() {
+                DisbandDistance::OwnedSc => owned_scs,
+                DisbandDistance::HomeSc => context.home_scs.get(*nation).unwrap_or(owned_scs),
+            };
+
+            // Get all regions in those supply centers. The rules require checking
             // distance to all coasts, so province precision is insufficient.
-            let owned_sc_regions = context
+            let sc_regions = context
                 .world
                 .regions()
-                .filter(|r| owned_scs.contains(r.province()))
+                .filter(|r| distance_scs.contains(r.province()))
+                .map(RegionKey::from)
                 .collect::<Vec<_>>();
 
             let mut units_by_disband_priority = units
                 .into_iter()
-                .map(|unit| {
+                .map(|(unit_type, region)| {
                     let unit_region = context
                         .world
-                        .find_region(&unit.1.to_string())
+                        .find_region(&region.to_string())
                         .unwrap_or_else(|| {
-                            panic!("Unit location {} should exist in world", unit.1)
+                            panic!("Unit location {} should exist in world", region)
                         });
 
-                    if owned_sc_regions.contains(&unit_region) {
-                        return (unit, 0);
+                    let distance = distance_to_nearest(
+                        context.world,
+                        &RegionKey::from(unit_region),
+                        &sc_regions,
+                    )
+                    .unwrap_or(u32::MAX);
+
+                    DisbandCandidate {
+                        nation,
+                        unit_type,
+                        region,
+                        distance,
                     }
-
-                    let min_distance = owned_sc_regions
-                        .iter()
-                        .filter_map(|sc_region| {
-                            // Using dijkstra because there isn't an obvious way to estimate
-                            // distance for A*, and the graph size is so small that the efficiency
-                            // difference shouldn't matter.
-                            petgraph::algo::dijkstra(
-                                &world_graph,
-                                unit_region,
-                                Some(sc_region),
-                                // Per DATC test 6.J.6, terrain is ingored in this
-                                // calculation. This is a deviation from older versions
-                                // of the DATC, which stated that sea units could only
-                                // consider sea distances
-                                |_| 1,
-                            )
-                            .get(sc_region)
-                            .copied()
-                        })
-                        .min()
-                        .unwrap_or(i32::MAX);
-
-                    (unit, min_distance)
                 })
                 .collect::<Vec<_>>();
 
-            // Per the DATC, units are sorted by distance from an owned SC. Equidistant fleets
-            // are disbanded before armies, and sorting of units within the same type is done
-            // alphabetically.
-            units_by_disband_priority.sort_by(|a, b| {
-                // Distance from nearest owned supply center, descending
-                b.1.cmp(&a.1)
-                    // when equidistant, disband fleets before armies
-                    .then(b.0 .0.cmp(&a.0 .0))
-                    // when units are same type and equidistant, disband in alphabetical order
-                    .then_with(|| a.0 .1.cmp(&b.0 .1))
-            });
+            units_by_disband_priority.sort_by(|a, b| context.rules.disband_priority(a, b));
 
             // Add units from the disband queue to the civil disorder output
-            self.civil_disorder
-                .extend(units_by_disband_priority.drain(0..usize_delta).map(|v| v.0));
+            self.civil_disorder.extend(
+                units_by_disband_priority
+                    .drain(0..usize_delta)
+                    .map(|v| (v.unit_type, v.region)),
+            );
 
             // Add the remaining units to the map of units that survive the turn.
             self.final_units.insert(
                 nation,
-                units_by_disband_priority.into_iter().map(|v| v.0).collect(),
+                units_by_disband_priority
+                    .into_iter()
+                    .map(|v| (v.unit_type, v.region))
+                    .collect(),
             );
         }
     }
@@ -355,22 +515,133 @@ impl<'a> Outcome<'a> {
     pub fn get(&self, order: &MappedBuildOrder) -> Option<&OrderOutcome> {
         self.orders.get(order)
     }
+
+    /// The units disbanded by civil disorder, sorted by region.
+    ///
+    /// Unlike iterating `civil_disorder`, the order of the result does not vary between runs.
+    pub fn civil_disorder_sorted(&self) -> Vec<(UnitType, RegionKey)> {
+        let mut units = self.civil_disorder.iter().cloned().collect::<Vec<_>>();
+        units.sort_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(&b.0)));
+        units
+    }
+
+    /// The number of successful builds and disbands ordered by each nation, as
+    /// `(builds, disbands)`.
+    ///
+    /// Nations with no successful orders are omitted. Units disbanded by civil disorder are not
+    /// counted; see [`Outcome::civil_disorder_sorted`].
+    pub fn tallies(&self) -> HashMap<&Nation, (u8, u8)> {
+        let mut tallies = HashMap::<&Nation, (u8, u8)>::default();
+        for (order, outcome) in &self.orders {
+            if *outcome != OrderOutcome::Succeeds {
+                continue;
+            }
+
+            let tally = tallies.entry(&order.nation).or_default();
+            match order.command {
+                BuildCommand::Build => tally.0 += 1,
+                BuildCommand::Disband => tally.1 += 1,
+            }
+        }
+
+        tallies
+    }
+
+    /// The position of every unit at the end of the build phase, sorted by region.
+    ///
+    /// Unlike iterating `final_units`, the order of the result does not vary between runs.
+    pub fn to_final_unit_positions_sorted(&self) -> Vec<UnitPosition<'static, RegionKey>> {
+        let mut positions = self
+            .final_units
+            .iter()
+            .flat_map(|(&nation, units)| {
+                units.iter().map(move |(unit_type, region)| {
+                    UnitPosition::new(
+                        Unit::new(Cow::Owned(nation.clone()), *unit_type),
+                        region.clone(),
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+        positions.sort_by(|a, b| {
+            a.region
+                .cmp(&b.region)
+                .then_with(|| a.nation().cmp(b.nation()))
+                .then(a.unit.unit_type().cmp(&b.unit.unit_type()))
+        });
+        positions
+    }
+}
+
+impl PhaseOutcome for Outcome<'_> {
+    type Order = MappedBuildOrder;
+    type OrderOutcome = OrderOutcome;
+
+    fn get(&self, order: &MappedBuildOrder) -> Option<&OrderOutcome> {
+        Outcome::get(self, order)
+    }
+
+    fn order_outcomes(&self) -> impl Iterator<Item = (&MappedBuildOrder, &OrderOutcome)> {
+        self.orders.iter().map(|(order, outcome)| (*order, outcome))
+    }
+}
+
+/// The units left at the end of a build phase, which can be the world state of a later build
+/// phase.
+impl WorldState for Outcome<'_> {
+    fn nations(&self) -> HashSet<&Nation> {
+        self.final_units.keys().copied().collect()
+    }
+
+    fn occupier(&self, province: &ProvinceKey) -> Option<&Nation> {
+        self.final_units
+            .iter()
+            .find(|(_, units)| {
+                units
+                    .iter()
+                    .any(|(_, region)| region.province() == province)
+            })
+            .map(|(&nation, _)| nation)
+    }
+
+    fn unit_count(&self, nation: &Nation) -> u8 {
+        self.final_units
+            .get(nation)
+            .map(|units| units.len())
+            .unwrap_or_default()
+            .try_into()
+            .unwrap()
+    }
+
+    fn units(&self, nation: &Nation) -> HashSet<(UnitType, RegionKey)> {
+        self.final_units.get(nation).cloned().unwrap_or_default()
+    }
 }
 
 /// Rulebook function for build-phase adjudication. This function does not worry about order quantities,
 /// and just focuses on whether or not a given build or disband command is otherwise valid.
-fn adjudicate(context: &Context<impl WorldState>, order: &MappedBuildOrder) -> OrderOutcome {
+fn adjudicate(
+    context: &Context<impl WorldState, impl Adjudicate>,
+    order: &MappedBuildOrder,
+) -> OrderOutcome {
     use self::OrderOutcome::*;
     let province = order.region.province();
 
     match order.command {
         BuildCommand::Build => {
-            if !context
-                .home_scs
-                .get(&order.nation)
-                .expect("Every nation should have home SCs")
-                .contains(province)
-            {
+            let is_build_site = match context.rules.build_policy() {
+                BuildPolicy::HomeOnly => context
+                    .home_scs
+                    .get(&order.nation)
+                    .expect("Every nation should have home SCs")
+                    .contains(province),
+                BuildPolicy::AnyOwnedSc => context
+                    .world
+                    .provinces()
+                    .any(|p| p == province && p.is_supply_center()),
+            };
+
+            if !is_build_site {
                 return InvalidProvince;
             }
 
@@ -403,25 +674,172 @@ fn adjudicate(context: &Context<impl WorldState>, order: &MappedBuildOrder) -> O
     }
 }
 
+/// The fewest borders crossed to reach any of `targets` from `start`, or `None` if none of
+/// them can be reached.
+///
+/// Per DATC test 6.J.6, terrain is ignored in this calculation. This is a deviation from older
+/// versions of the DATC, which stated that sea units could only consider sea distances.
+fn distance_to_nearest(world: &Map, start: &RegionKey, targets: &[RegionKey]) -> Option<u32> {
+    let mut visited = HashSet::default();
+    visited.insert(start);
+    let mut frontier = vec![start];
+    let mut distance = 0;
+
+    while !frontier.is_empty() {
+        if frontier.iter().any(|region| targets.contains(region)) {
+            return Some(distance);
+        }
+
+        distance += 1;
+        frontier = frontier
+            .into_iter()
+            .flat_map(|region| world.find_bordering(region))
+            .filter(|region| visited.insert(*region))
+            .collect();
+    }
+
+    None
+}
+
 /// Convert a map into an initial ownership state where each nation owns their home
 /// supply centers and all other supply centers are unowned.
+///
+/// This is the same as [`Map::initial_ownership`].
 pub fn to_initial_ownerships(map: &Map) -> HashMap<ProvinceKey, Nation> {
-    map.provinces()
-        .filter_map(|province| {
-            if let SupplyCenter::Home(nat) = &province.supply_center {
-                Some((province.into(), nat.clone()))
-            } else {
-                None
-            }
-        })
-        .collect()
+    map.initial_ownership()
+}
+
+/// The supply center ownerships after each unit in `positions` captures the supply center it
+/// occupies, as happens at the end of a fall turn. Unoccupied supply centers keep their owner
+/// in `ownerships`.
+pub fn capture_supply_centers(
+    map: &Map,
+    ownerships: &HashMap<ProvinceKey, Nation>,
+    positions: &impl UnitPositions<RegionKey>,
+) -> HashMap<ProvinceKey, Nation> {
+    let supply_centers = map
+        .provinces()
+        .filter(|p| p.is_supply_center())
+        .map(ProvinceKey::from)
+        .collect::<HashSet<_>>();
+
+    let mut captured = ownerships.clone();
+    for pos in positions.unit_positions() {
+        let province = pos.region.province();
+        if supply_centers.contains(province) {
+            captured.insert(province.clone(), pos.nation().clone());
+        }
+    }
+
+    captured
+}
+
+/// The supply centers which changed hands between two ownership states, grouped by nation.
+///
+/// Create with [`diff_ownerships`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OwnershipDiff {
+    gained: HashMap<Nation, Vec<ProvinceKey>>,
+    lost: HashMap<Nation, Vec<ProvinceKey>>,
+}
+
+impl OwnershipDiff {
+    /// The supply centers `nation` owns after the change but did not own before, sorted by
+    /// province.
+    pub fn gained(&self, nation: &Nation) -> &[ProvinceKey] {
+        self.gained
+            .get(nation)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// The supply centers `nation` owned before the change but does not own after, sorted by
+    /// province.
+    pub fn lost(&self, nation: &Nation) -> &[ProvinceKey] {
+        self.lost.get(nation).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// The nations which gained or lost at least one supply center.
+    pub fn nations(&self) -> impl Iterator<Item = &Nation> {
+        let mut nations = self
+            .gained
+            .keys()
+            .chain(self.lost.keys())
+            .collect::<Vec<_>>();
+        nations.sort();
+        nations.dedup();
+        nations.into_iter()
+    }
+
+    /// Whether no supply center changed hands.
+    pub fn is_empty(&self) -> bool {
+        self.gained.is_empty() && self.lost.is_empty()
+    }
+}
+
+/// Compare two supply center ownership states, such as the ownerships before and after a
+/// winter adjustment phase, and find which centers each nation gained and lost.
+///
+/// A province missing from one of the maps is treated as unowned in that state.
+pub fn diff_ownerships(
+    before: &HashMap<ProvinceKey, Nation>,
+    after: &HashMap<ProvinceKey, Nation>,
+) -> OwnershipDiff {
+    let mut diff = OwnershipDiff::default();
+
+    for province in before.keys().chain(after.keys()).collect::<HashSet<_>>() {
+        let old_owner = before.get(province);
+        let new_owner = after.get(province);
+        if old_owner == new_owner {
+            continue;
+        }
+
+        if let Some(nation) = old_owner {
+            diff.lost
+                .entry(nation.clone())
+                .or_default()
+                .push(province.clone());
+        }
+
+        if let Some(nation) = new_owner {
+            diff.gained
+                .entry(nation.clone())
+                .or_default()
+                .push(province.clone());
+        }
+    }
+
+    for provinces in diff.gained.values_mut().chain(diff.lost.values_mut()) {
+        provinces.sort();
+    }
+
+    diff
+}
+
+/// Get the regions of a province in which a unit of the given type could be built, sorted
+/// by region.
+///
+/// Build orders must name a region the unit can occupy, so a fleet built in a province with
+/// multiple coasts - such as St Petersburg - must specify a coast. This returns each of those
+/// coasts as an option. For armies and provinces with a single coast, this returns the
+/// province's only region. If the unit cannot occupy any region of the province, this is empty.
+///
+/// This does not check whether the province is a valid build site for any nation.
+pub fn buildable_coasts(map: &Map, province: &ProvinceKey, unit_type: UnitType) -> Vec<RegionKey> {
+    let mut regions = map
+        .regions()
+        .filter(|r| r.province() == province && unit_type.can_occupy(r.terrain()))
+        .map(RegionKey::from)
+        .collect::<Vec<_>>();
+    regions.sort();
+    regions
 }
 
 #[cfg(test)]
 mod tests {
-    use super::to_initial_ownerships;
-    use crate::geo::{standard_map, ProvinceKey};
-    use crate::Nation;
+    use super::{buildable_coasts, diff_ownerships, to_initial_ownerships};
+    use crate::geo::{standard_map, Coast, ProvinceKey, RegionKey};
+    use crate::{Nation, UnitType};
 
     #[test]
     fn to_initial_ownerships_for_standard_map() {
@@ -434,4 +852,49 @@ mod tests {
 
         assert_eq!(None, ownerships.get(&ProvinceKey::from("bel")));
     }
+
+    #[test]
+    fn diff_ownerships_after_capture() {
+        let before = to_initial_ownerships(standard_map());
+        let mut after = before.clone();
+        after.insert(ProvinceKey::from("ber"), Nation::from("RUS"));
+
+        let diff = diff_ownerships(&before, &after);
+        assert_eq!(
+            diff.gained(&Nation::from("RUS")),
+            &[ProvinceKey::from("ber")]
+        );
+        assert_eq!(diff.lost(&Nation::from("GER")), &[ProvinceKey::from("ber")]);
+        assert!(diff.gained(&Nation::from("GER")).is_empty());
+        assert!(diff.lost(&Nation::from("RUS")).is_empty());
+        assert_eq!(
+            diff.nations().collect::<Vec<_>>(),
+            vec![&Nation::from("GER"), &Nation::from("RUS")]
+        );
+
+        assert!(diff_ownerships(&before, &before).is_empty());
+    }
+
+    #[test]
+    fn buildable_coasts_for_standard_map() {
+        let stp = ProvinceKey::from("stp");
+        assert_eq!(
+            buildable_coasts(standard_map(), &stp, UnitType::Fleet),
+            vec![
+                RegionKey::new("stp", Coast::North),
+                RegionKey::new("stp", Coast::South)
+            ]
+        );
+        assert_eq!(
+            buildable_coasts(standard_map(), &stp, UnitType::Army),
+            vec![RegionKey::new("stp", None)]
+        );
+        assert_eq!(
+            buildable_coasts(standard_map(), &ProvinceKey::from("bre"), UnitType::Fleet),
+            vec![RegionKey::new("bre", None)]
+        );
+        assert!(
+            buildable_coasts(standard_map(), &ProvinceKey::from("mos"), UnitType::Fleet).is_empty()
+        );
+    }
 }
@@ -3,6 +3,7 @@ use super::{convoy, support};
 use super::{Adjudicate, Context, MappedMainOrder, ResolverState};
 use crate::order::{Command, MainCommand};
 use crate::{geo::ProvinceKey, ShortName};
+use alloc::vec::Vec;
 
 /// Returns true if `order` is a move AND between the source and dest, either:
 ///
@@ -180,7 +181,7 @@ mod tests {
         .map(|ord| ord.parse::<MappedMainOrder>().unwrap())
         .collect::<Vec<_>>();
 
-        let context = Context::new(crate::geo::standard_map(), Rulebook, &orders);
+        let context = Context::new(crate::geo::standard_map(), Rulebook::default(), &orders);
         let mut state = ResolverState::new();
 
         assert_eq!(
@@ -204,7 +205,7 @@ mod tests {
         .map(|ord| ord.parse::<MappedMainOrder>().unwrap())
         .collect::<Vec<_>>();
 
-        let context = Context::new(crate::geo::standard_map(), Rulebook, &orders);
+        let context = Context::new(crate::geo::standard_map(), Rulebook::default(), &orders);
         let mut state = ResolverState::new();
         let nth_prevent = max_prevent_result(&context, &mut state, &orders[3]);
         let swe_prevent = max_prevent_result(&context, &mut state, &orders[4]);
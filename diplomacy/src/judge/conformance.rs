@@ -0,0 +1,100 @@
+//! A harness for checking an adjudicator against known cases, such as the DATC.
+
+use super::{Adjudicate, MappedMainOrder, OrderState, Submission};
+use crate::geo::Map;
+use alloc::{format, string::String, vec::Vec};
+
+/// A set of orders and the expected success or failure of some of them.
+///
+/// The starting positions are inferred from the orders, as in the DATC.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConformanceCase {
+    /// A name for the case, such as its DATC number.
+    pub name: String,
+    /// The orders submitted for the turn.
+    pub orders: Vec<MappedMainOrder>,
+    /// The expected state of each order whose outcome the case checks.
+    pub expected: Vec<(MappedMainOrder, OrderState)>,
+}
+
+impl ConformanceCase {
+    pub fn new(
+        name: impl Into<String>,
+        orders: Vec<MappedMainOrder>,
+        expected: Vec<(MappedMainOrder, OrderState)>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            orders,
+            expected,
+        }
+    }
+}
+
+/// The result of running one [`ConformanceCase`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaseResult {
+    /// The name of the case.
+    pub name: String,
+    /// An explanation of each expectation the adjudicator did not meet.
+    pub failures: Vec<String>,
+}
+
+impl CaseResult {
+    /// Whether the adjudicator met every expectation of the case.
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// The results of [`run_conformance`], in the order the cases were given.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConformanceReport {
+    pub results: Vec<CaseResult>,
+}
+
+impl ConformanceReport {
+    /// Whether every case passed.
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(CaseResult::passed)
+    }
+
+    /// The cases which did not pass.
+    pub fn failed(&self) -> impl Iterator<Item = &CaseResult> {
+        self.results.iter().filter(|result| !result.passed())
+    }
+}
+
+/// Adjudicate each case on `world_map` with `adjudicator` and compare the outcomes to the
+/// case's expectations.
+pub fn run_conformance<A: Adjudicate + Clone>(
+    world_map: &Map,
+    adjudicator: A,
+    cases: impl IntoIterator<Item = ConformanceCase>,
+) -> ConformanceReport {
+    let results = cases
+        .into_iter()
+        .map(|case| {
+            let submission = Submission::with_inferred_state(world_map, case.orders);
+            let outcome = submission.adjudicate(adjudicator.clone());
+            let failures = case
+                .expected
+                .iter()
+                .filter_map(|(order, expected)| match outcome.get(order) {
+                    None => Some(format!("{order}: not in outcome")),
+                    Some(actual) if OrderState::from(actual) != *expected => {
+                        Some(format!("{order}: expected {expected:?}, got {actual}"))
+                    }
+                    Some(_) => None,
+                })
+                .collect();
+
+            CaseResult {
+                name: case.name,
+                failures,
+            }
+        })
+        .collect();
+
+    ConformanceReport { results }
+}
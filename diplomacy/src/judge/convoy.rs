@@ -1,7 +1,10 @@
-use super::{Adjudicate, Context, MappedMainOrder, OrderState, ResolverState};
+use super::{calc, Adjudicate, Context, MappedMainOrder, OrderState, ResolverState};
+use crate::collections::HashMap;
 use crate::geo::{Map, ProvinceKey, RegionKey, Terrain};
 use crate::order::{Command, MainCommand};
 use crate::{UnitPosition, UnitType};
+use alloc::{string::ToString, vec, vec::Vec};
+use core::cell::RefCell;
 
 /// Failure cases for convoy route lookup.
 pub enum ConvoyRouteError {
@@ -10,6 +13,9 @@ pub enum ConvoyRouteError {
 
     /// Hold, support, and convoy orders cannot be convoyed.
     CanOnlyConvoyMove,
+
+    /// The move order forbade the use of a convoy.
+    ConvoyForbidden,
 }
 
 /// The outcome of a convoy order.
@@ -24,6 +30,23 @@ pub enum ConvoyOutcome<O> {
     Paradox,
     /// The convoy was not disrupted. This doesn't mean the move necessarily succeeded.
     NotDisrupted,
+    /// The convoying unit was attacked by another nation's unit but not dislodged, so the
+    /// convoy was not disrupted. This doesn't mean the move necessarily succeeded.
+    SurvivedAttack,
+}
+
+/// How a unit travelled, or would have travelled, to the destination of its move order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MoveRoute {
+    /// The unit moved across a shared border.
+    Direct,
+    /// The unit was carried by a convoy.
+    ///
+    /// An army moving to an adjacent province uses a convoy whenever an undisrupted convoy
+    /// route exists and the order does not forbid it. Such an army does not engage in a
+    /// head-to-head battle with a unit moving the opposite way.
+    Convoyed,
 }
 
 impl<O> ConvoyOutcome<O> {
@@ -35,13 +58,17 @@ impl<O> ConvoyOutcome<O> {
             Dislodged(by) => Dislodged(map_fn(by)),
             Paradox => Paradox,
             NotDisrupted => NotDisrupted,
+            SurvivedAttack => SurvivedAttack,
         }
     }
 }
 
 impl<O> From<&'_ ConvoyOutcome<O>> for OrderState {
     fn from(other: &ConvoyOutcome<O>) -> Self {
-        if matches!(other, ConvoyOutcome::NotDisrupted) {
+        if matches!(
+            other,
+            ConvoyOutcome::NotDisrupted | ConvoyOutcome::SurvivedAttack
+        ) {
             OrderState::Succeeds
         } else {
             OrderState::Fails
@@ -64,11 +91,19 @@ fn is_convoy_for(convoy: &MappedMainOrder, mv_ord: &MappedMainOrder) -> bool {
     }
 }
 
+/// Checks whether `mv_ord` is a move which refuses to travel by convoy.
+fn forbids_convoy(mv_ord: &MappedMainOrder) -> bool {
+    match &mv_ord.command {
+        MainCommand::Move(cmd) => cmd.forbids_convoy(),
+        _ => false,
+    }
+}
+
 trait RouteStep: Eq + Clone {
     fn region(&self) -> &RegionKey;
 }
 
-impl<'a> RouteStep for &'a MappedMainOrder {
+impl RouteStep for &MappedMainOrder {
     fn region(&self) -> &RegionKey {
         &self.region
     }
@@ -80,19 +115,23 @@ impl<'a> RouteStep for UnitPosition<'a> {
     }
 }
 
-/// Find all routes from `origin` to `dest` given a set of valid convoys.
+/// Find all routes from `origin` to `dest` given a set of valid convoys, using at most
+/// `max_len` convoys in each route if a limit is provided.
 fn route_steps<R: RouteStep>(
     map: &Map,
     convoys: &[R],
     origin: &ProvinceKey,
     dest: &ProvinceKey,
     working_path: Vec<R>,
+    max_len: Option<usize>,
 ) -> Vec<Vec<R>> {
     let adjacent_regions = map.find_bordering(origin);
     // if we've got a convoy going and there is one hop to the destination,
     // we've found a valid solution.
     if !working_path.is_empty() && adjacent_regions.iter().any(|&r| r == dest) {
         vec![working_path]
+    } else if max_len.is_some_and(|max| working_path.len() >= max) {
+        vec![]
     } else {
         let mut paths = vec![];
         for convoy in convoys {
@@ -100,8 +139,14 @@ fn route_steps<R: RouteStep>(
             if !working_path.contains(convoy) && adjacent_regions.contains(&convoy.region()) {
                 let mut next_path = working_path.clone();
                 next_path.push(convoy.clone());
-                let mut steps =
-                    route_steps(map, convoys, convoy.region().province(), dest, next_path);
+                let mut steps = route_steps(
+                    map,
+                    convoys,
+                    convoy.region().province(),
+                    dest,
+                    next_path,
+                    max_len,
+                );
                 if !steps.is_empty() {
                     paths.append(&mut steps);
                 }
@@ -112,6 +157,74 @@ fn route_steps<R: RouteStep>(
     }
 }
 
+/// Memoized results of convoy route searches within a single resolution.
+///
+/// Routes depend only on the map, the endpoints of the move, and which convoy orders are
+/// successful. The successful convoys are part of the key, so when a guess flips the
+/// state of a convoy order, later searches look up a different entry rather than reusing
+/// a stale one. This means entries never need to be invalidated.
+#[derive(Default)]
+pub(in crate::judge) struct RouteCache {
+    /// Routes are stored as indices into the convoys of the query.
+    routes: RefCell<HashMap<RouteQuery, Vec<Vec<usize>>>>,
+}
+
+#[derive(PartialEq, Eq, Hash)]
+struct RouteQuery {
+    origin: ProvinceKey,
+    dest: ProvinceKey,
+    convoys: Vec<RegionKey>,
+}
+
+impl RouteCache {
+    /// Find all routes from `origin` to `dest` using `convoys`, reusing the result of an
+    /// earlier identical search if there was one.
+    ///
+    /// The cache belongs to a single context, so `max_len` is the same for every query.
+    fn routes<'a>(
+        &self,
+        map: &Map,
+        origin: &ProvinceKey,
+        dest: &ProvinceKey,
+        convoys: &[&'a MappedMainOrder],
+        max_len: Option<usize>,
+    ) -> Vec<Vec<&'a MappedMainOrder>> {
+        let query = RouteQuery {
+            origin: origin.clone(),
+            dest: dest.clone(),
+            convoys: convoys.iter().map(|c| c.region.clone()).collect(),
+        };
+
+        let mut cache = self.routes.borrow_mut();
+        let steps = cache.entry(query).or_insert_with(|| {
+            let indexed = convoys
+                .iter()
+                .enumerate()
+                .map(|(idx, convoy)| (ConvoyIndex(idx), &convoy.region))
+                .collect::<Vec<_>>();
+
+            route_steps(map, &indexed, origin, dest, vec![], max_len)
+                .into_iter()
+                .map(|route| route.into_iter().map(|(idx, _)| idx.0).collect())
+                .collect()
+        });
+
+        steps
+            .iter()
+            .map(|route| route.iter().map(|&idx| convoys[idx]).collect())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ConvoyIndex(usize);
+
+impl RouteStep for (ConvoyIndex, &RegionKey) {
+    fn region(&self) -> &RegionKey {
+        self.1
+    }
+}
+
 /// Finds all valid convoy routes for a given move order.
 pub fn routes<'a>(
     ctx: &Context<'a, impl Adjudicate>,
@@ -120,6 +233,8 @@ pub fn routes<'a>(
 ) -> Result<Vec<Vec<&'a MappedMainOrder>>, ConvoyRouteError> {
     if mv_ord.unit_type == UnitType::Fleet {
         Err(ConvoyRouteError::CanOnlyConvoyArmy)
+    } else if forbids_convoy(mv_ord) {
+        Err(ConvoyRouteError::ConvoyForbidden)
     } else if let Some(dst) = mv_ord.move_dest() {
         // Get the convoy orders that can ferry the provided move order and are
         // successful. Per http://uk.diplom.org/pouch/Zine/S2009M/Kruijswijk/DipMath_Chp6.htm
@@ -132,12 +247,12 @@ pub fn routes<'a>(
             }
         }
 
-        Ok(route_steps(
+        Ok(ctx.route_cache.routes(
             ctx.world_map,
-            &convoy_steps,
             mv_ord.region.province(),
             dst.province(),
-            vec![],
+            &convoy_steps,
+            ctx.rules.policies().max_convoy_length,
         ))
     } else {
         Err(ConvoyRouteError::CanOnlyConvoyMove)
@@ -155,17 +270,61 @@ pub fn route_exists<'a>(
         .unwrap_or(false)
 }
 
+/// Determines how a move order travels to its destination, or `None` if it has no path.
+pub fn move_route<'a>(
+    ctx: &Context<'a, impl Adjudicate>,
+    state: &mut ResolverState<'a>,
+    mv_ord: &MappedMainOrder,
+) -> Option<MoveRoute> {
+    if !calc::path_exists(ctx, state, mv_ord) {
+        None
+    } else if route_exists(ctx, state, mv_ord) {
+        Some(MoveRoute::Convoyed)
+    } else {
+        Some(MoveRoute::Direct)
+    }
+}
+
+/// Determines if a convoy route exists for the given move order using every convoy ordered
+/// for it, regardless of whether those convoys succeed.
+pub fn ordered_route_exists(ctx: &Context<'_, impl Adjudicate>, mv_ord: &MappedMainOrder) -> bool {
+    if mv_ord.unit_type == UnitType::Fleet || forbids_convoy(mv_ord) {
+        return false;
+    }
+
+    let Some(dst) = mv_ord.move_dest() else {
+        return false;
+    };
+
+    let convoys = ctx
+        .orders()
+        .filter(|order| is_convoy_for(order, mv_ord))
+        .collect::<Vec<_>>();
+
+    !ctx.route_cache
+        .routes(
+            ctx.world_map,
+            mv_ord.region.province(),
+            dst.province(),
+            &convoys,
+            ctx.rules.policies().max_convoy_length,
+        )
+        .is_empty()
+}
+
 /// Checks if a convoy route may exist for an order, based on the positions
 /// of fleets, the move order's source region, and the destination region.
 ///
 /// This is used before adjudication to identify illegal orders, so it does
-/// not take in a full context.
+/// not take in a full context. If `max_len` is provided, only routes using at
+/// most that many fleets are considered.
 pub fn route_may_exist<'a>(
     map: &'a Map,
     unit_positions: impl IntoIterator<Item = UnitPosition<'a>>,
     mv_ord: &MappedMainOrder,
+    max_len: Option<usize>,
 ) -> bool {
-    if mv_ord.unit_type == UnitType::Fleet {
+    if mv_ord.unit_type == UnitType::Fleet || forbids_convoy(mv_ord) {
         return false;
     }
 
@@ -190,6 +349,7 @@ pub fn route_may_exist<'a>(
         mv_ord.region.province(),
         dst.province(),
         vec![],
+        max_len,
     );
 
     !steps.is_empty()
@@ -200,7 +360,7 @@ mod test {
     use crate::geo::{self, ProvinceKey, RegionKey};
     use crate::judge::MappedMainOrder;
     use crate::order::{ConvoyedMove, Order};
-    use crate::UnitType;
+    use crate::{UnitPosition, UnitType};
 
     fn convoy(l: &str, f: &str, t: &str) -> MappedMainOrder {
         Order::new(
@@ -217,7 +377,7 @@ mod test {
 
     #[test]
     fn pathfinder() {
-        let convoys = vec![
+        let convoys = [
             convoy("ska", "lon", "swe"),
             convoy("eng", "lon", "swe"),
             convoy("nth", "lon", "swe"),
@@ -230,6 +390,7 @@ mod test {
             &ProvinceKey::new("lon"),
             &ProvinceKey::new("swe"),
             vec![],
+            None,
         );
         for r in &routes {
             println!("CHAIN");
@@ -240,4 +401,75 @@ mod test {
 
         assert_eq!(2, routes.len());
     }
+
+    #[test]
+    fn route_may_exist_respects_max_len() {
+        let army = |l: &str, t: &str| -> MappedMainOrder {
+            format!("ENG: A {} -> {}", l, t).parse().unwrap()
+        };
+        let fleets = ["ENG: F eng", "ENG: F nth"]
+            .into_iter()
+            .map(|f| f.parse::<UnitPosition<RegionKey>>().unwrap())
+            .collect::<Vec<_>>();
+        let positions = || fleets.iter().map(UnitPosition::as_region_ref);
+
+        let long = army("bre", "nwy");
+        assert!(super::route_may_exist(
+            geo::standard_map(),
+            positions(),
+            &long,
+            None
+        ));
+        assert!(super::route_may_exist(
+            geo::standard_map(),
+            positions(),
+            &long,
+            Some(2)
+        ));
+        assert!(!super::route_may_exist(
+            geo::standard_map(),
+            positions(),
+            &long,
+            Some(1)
+        ));
+
+        let short = army("lon", "bel");
+        assert!(super::route_may_exist(
+            geo::standard_map(),
+            positions(),
+            &short,
+            Some(1)
+        ));
+    }
+
+    #[test]
+    fn route_cache_matches_pathfinder() {
+        let convoys = [
+            convoy("ska", "lon", "swe"),
+            convoy("eng", "lon", "swe"),
+            convoy("nth", "lon", "swe"),
+            convoy("nwg", "lon", "swe"),
+        ];
+        let convoys = convoys.iter().collect::<Vec<_>>();
+        let lon = ProvinceKey::new("lon");
+        let swe = ProvinceKey::new("swe");
+
+        let expected = super::route_steps(geo::standard_map(), &convoys, &lon, &swe, vec![], None);
+        let cache = super::RouteCache::default();
+        assert_eq!(
+            cache.routes(geo::standard_map(), &lon, &swe, &convoys, None),
+            expected
+        );
+        assert_eq!(
+            cache.routes(geo::standard_map(), &lon, &swe, &convoys, None),
+            expected
+        );
+        assert_eq!(cache.routes.borrow().len(), 1);
+
+        // A different set of successful convoys is a different query.
+        assert!(cache
+            .routes(geo::standard_map(), &lon, &swe, &convoys[2..], None)
+            .is_empty());
+        assert_eq!(cache.routes.borrow().len(), 2);
+    }
 }
@@ -0,0 +1,94 @@
+//! The state of a game between phases, without the static map or any orders.
+
+use super::{build, retreat};
+use crate::collections::HashMap;
+#[cfg(feature = "std")]
+use crate::geo::map_by_name;
+use crate::geo::{Map, ProvinceKey, RegionKey};
+use crate::{Nation, Time, UnitPosition};
+use alloc::{string::String, vec::Vec};
+
+/// The parts of a game that change from phase to phase: where the units are, who owns each
+/// supply center, and when the game is.
+///
+/// The map is referenced by name rather than included, so this is what a server needs to
+/// persist between phases. Unlike a [`SubmissionSnapshot`](super::SubmissionSnapshot), it
+/// does not include orders.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameState {
+    /// The [name](Map::name) of the map.
+    pub map: String,
+    /// The next phase to be played.
+    pub time: Time,
+    /// The units on the board, sorted by region.
+    pub positions: Vec<UnitPosition<'static, RegionKey>>,
+    /// The supply center ownerships.
+    pub ownerships: HashMap<ProvinceKey, Nation>,
+}
+
+impl GameState {
+    /// Create the state of a game on `world_map`, sorting `positions` by region.
+    pub fn new(
+        world_map: &Map,
+        time: Time,
+        positions: impl IntoIterator<Item = UnitPosition<'static, RegionKey>>,
+        ownerships: HashMap<ProvinceKey, Nation>,
+    ) -> Self {
+        let mut positions = positions.into_iter().collect::<Vec<_>>();
+        positions.sort_by(|a, b| a.region.cmp(&b.region));
+
+        Self {
+            map: world_map.name().into(),
+            time,
+            positions,
+            ownerships,
+        }
+    }
+
+    /// Create the state after a retreat phase on `world_map`, ready for the phase at `time`.
+    ///
+    /// `ownerships` are the supply center ownerships at the start of the turn. If `time` is a
+    /// build phase, the retreat phase ended a fall turn, so each unit captures the supply
+    /// center it occupies; see [`build::capture_supply_centers`]. If a turn dislodged no
+    /// units, resolve its retreat phase without orders to get an outcome to pass here.
+    pub fn from_retreat(
+        world_map: &Map,
+        outcome: &retreat::Outcome<'_>,
+        ownerships: &HashMap<ProvinceKey, Nation>,
+        time: Time,
+    ) -> Self {
+        let ownerships = if time.phase().is_build() {
+            build::capture_supply_centers(world_map, ownerships, outcome)
+        } else {
+            ownerships.clone()
+        };
+
+        Self::new(world_map, time, outcome.resulting_positions(), ownerships)
+    }
+
+    /// Create the state after a build phase on `world_map`, ready for the phase at `time`.
+    ///
+    /// `ownerships` are the supply center ownerships at the end of the build phase, from
+    /// [`build::Context::to_ownerships`].
+    pub fn from_build(
+        world_map: &Map,
+        outcome: &build::Outcome<'_>,
+        ownerships: HashMap<ProvinceKey, Nation>,
+        time: Time,
+    ) -> Self {
+        Self::new(
+            world_map,
+            time,
+            outcome.to_final_unit_positions_sorted(),
+            ownerships,
+        )
+    }
+
+    /// The map identified by the state, or `None` if the map is not recognized. Only
+    /// built-in maps, which can be found with [`map_by_name`], are recognized.
+    #[cfg(feature = "std")]
+    pub fn world_map(&self) -> Option<&'static Map> {
+        map_by_name(&self.map)
+    }
+}
@@ -2,27 +2,45 @@
 
 pub mod build;
 mod calc;
+#[cfg(feature = "testing")]
+mod conformance;
 mod convoy;
+mod game_state;
 mod outcome;
+mod phase;
 mod resolver;
 pub mod retreat;
 mod rulebook;
+mod snapshot;
+mod stalemate;
 mod state_type;
 mod strength;
 pub mod support;
+mod trace;
 
-pub use self::outcome::{IllegalOrder, OrderOutcome, Outcome};
+pub use self::game_state::GameState;
+pub use self::outcome::{IllegalOrder, OrderOutcome, Outcome, OutcomeDiff, TurnEvent, TurnSummary};
+pub use self::phase::PhaseOutcome;
 pub use self::state_type::OrderState;
 
-pub use self::convoy::ConvoyOutcome;
+#[cfg(feature = "testing")]
+pub use self::conformance::{run_conformance, CaseResult, ConformanceCase, ConformanceReport};
+pub use self::convoy::{ConvoyOutcome, MoveRoute};
 pub use self::rulebook::AttackOutcome;
 pub use self::rulebook::HoldOutcome;
 use self::strength::Prevent;
-pub use self::support::SupportOutcome;
+pub use self::support::{SupportKind, SupportOutcome};
 
-pub use self::resolver::{Context, ResolverState, Submission};
-pub use self::rulebook::Rulebook;
-use crate::geo::{Border, RegionKey, Terrain};
+#[cfg(feature = "resolution-stats")]
+pub use self::resolver::ResolutionStats;
+#[cfg(feature = "parallel")]
+pub use self::resolver::MIN_PARALLEL_ORDERS;
+pub use self::resolver::{Context, IllegalOrderPolicy, ResolverState, Submission};
+pub use self::rulebook::{HelpDislodgingOwnUnit, Rulebook, SelfDislodgement};
+pub use self::snapshot::SubmissionSnapshot;
+pub use self::stalemate::{is_stalemate_line, is_static};
+pub use self::trace::{TraceEntry, Tracing};
+use crate::geo::{Border, RegionKey};
 use crate::order::{BuildOrder, MainCommand, Order, RetreatOrder};
 use crate::UnitType;
 
@@ -31,35 +49,71 @@ pub type MappedBuildOrder = BuildOrder<RegionKey>;
 pub type MappedRetreatOrder = RetreatOrder<RegionKey>;
 
 /// A clonable container for a rulebook which can be used to adjudicate a turn.
+///
+/// The context passed to each method may hold different rules than `self`, such as when
+/// one adjudicator wraps another. Implementations should use the context's rules, rather
+/// than `self`, when resolving other orders.
 pub trait Adjudicate: Sized {
     /// Determine the success of an order.
     fn adjudicate<'a>(
         &self,
-        context: &Context<'a, Self>,
+        context: &Context<'a, impl Adjudicate>,
         resolver: &mut ResolverState<'a>,
         order: &'a MappedMainOrder,
     ) -> OrderState;
 
     fn explain<'a>(
         &self,
-        context: &Context<'a, Self>,
+        context: &Context<'a, impl Adjudicate>,
         resolver: &mut ResolverState<'a>,
         order: &'a MappedMainOrder,
     ) -> OrderOutcome<&'a MappedMainOrder>;
+
+    /// The rule variants to apply, such as whether dislodging a supporting unit cuts its
+    /// support. Resolution reads every policy from here, so an adjudicator which wraps
+    /// another should return the wrapped adjudicator's policies.
+    fn policies(&self) -> &Rulebook;
+
+    /// Extra strength the unit given `order` has when resisting attacks on its own province,
+    /// such as from a fortress in a variant. This is added to the unit's hold strength, and
+    /// to its defend strength in a head-to-head battle.
+    ///
+    /// The standard rules have no such bonus.
+    fn defense_bonus(&self, _order: &MappedMainOrder) -> usize {
+        0
+    }
 }
 
-impl Border {
-    fn is_passable_by(&self, unit_type: UnitType) -> bool {
-        unit_type.can_occupy(self.terrain())
+impl<T: Adjudicate> Adjudicate for &T {
+    fn adjudicate<'a>(
+        &self,
+        context: &Context<'a, impl Adjudicate>,
+        resolver: &mut ResolverState<'a>,
+        order: &'a MappedMainOrder,
+    ) -> OrderState {
+        (*self).adjudicate(context, resolver, order)
+    }
+
+    fn explain<'a>(
+        &self,
+        context: &Context<'a, impl Adjudicate>,
+        resolver: &mut ResolverState<'a>,
+        order: &'a MappedMainOrder,
+    ) -> OrderOutcome<&'a MappedMainOrder> {
+        (*self).explain(context, resolver, order)
+    }
+
+    fn policies(&self) -> &Rulebook {
+        (*self).policies()
+    }
+
+    fn defense_bonus(&self, order: &MappedMainOrder) -> usize {
+        (*self).defense_bonus(order)
     }
 }
 
-impl UnitType {
-    fn can_occupy(self, terrain: Terrain) -> bool {
-        match terrain {
-            Terrain::Coast => true,
-            Terrain::Land => self == UnitType::Army,
-            Terrain::Sea => self == UnitType::Fleet,
-        }
+impl Border {
+    fn is_passable_by(&self, unit_type: UnitType) -> bool {
+        unit_type.can_occupy(self.terrain())
     }
 }
@@ -1,20 +1,37 @@
+#[cfg(feature = "resolution-stats")]
+use super::ResolutionStats;
 use super::{
-    retreat, Adjudicate, AttackOutcome, Context, ConvoyOutcome, HoldOutcome, MappedMainOrder,
-    OrderState, ResolverState, SupportOutcome,
+    build, calc, convoy, retreat, support, Adjudicate, AttackOutcome, Context, ConvoyOutcome,
+    HoldOutcome, MappedMainOrder, MoveRoute, OrderState, PhaseOutcome, ResolverState,
+    SelfDislodgement, Submission, SupportKind, SupportOutcome,
 };
+use crate::collections::HashMap;
+use crate::geo::{ProvinceKey, RegionKey};
+use crate::order::{Command, MainCommand, Order};
+use crate::{Nation, ShortName, Unit, UnitPosition};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt;
 use from_variants::FromVariants;
-use std::collections::HashMap;
-use std::fmt;
 
 /// The outcome of a specific order. The variant of the outcome will match the issued order
-/// type.
+/// type, unless the order was illegal.
 #[derive(FromVariants, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OrderOutcome<O> {
+    /// The order was illegal and was ignored during adjudication, regardless of its command.
     Illegal(IllegalOrder),
+    /// The outcome of a hold order.
     Hold(HoldOutcome<O>),
+    /// The outcome of a move order.
     Move(AttackOutcome<O>),
+    /// The outcome of a support order.
     Support(SupportOutcome<O>),
+    /// The outcome of a convoy order.
     Convoy(ConvoyOutcome<O>),
 }
 
@@ -30,6 +47,17 @@ impl<O> OrderOutcome<O> {
             Convoy(oo) => Convoy(oo.map_order(map_fn)),
         }
     }
+
+    /// Collapse the outcome into whether or not the order succeeded.
+    pub fn to_order_state(&self) -> OrderState {
+        self.into()
+    }
+
+    /// Whether the order succeeded. For moves, this means the unit moved; for all
+    /// other orders, it means the order was not disrupted.
+    pub fn is_successful(&self) -> bool {
+        self.to_order_state().into()
+    }
 }
 
 impl<O> From<&'_ OrderOutcome<O>> for OrderState {
@@ -62,6 +90,49 @@ impl<O: fmt::Debug> fmt::Debug for OrderOutcome<O> {
     }
 }
 
+/// A concise description for logs, such as `Prevented by GER: A mun -> sil`.
+impl<O: fmt::Display> fmt::Display for OrderOutcome<O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OrderOutcome::Illegal(reason) => match reason {
+                IllegalOrder::NoUnit => write!(f, "Illegal: no unit"),
+                IllegalOrder::ForeignUnit => write!(f, "Illegal: foreign unit"),
+                IllegalOrder::MultipleToSameUnit => {
+                    write!(f, "Illegal: multiple orders to the same unit")
+                }
+                IllegalOrder::UnreachableDestination => {
+                    write!(f, "Illegal: unreachable destination")
+                }
+            },
+            OrderOutcome::Hold(HoldOutcome::Succeeds) => write!(f, "Succeeds"),
+            OrderOutcome::Hold(HoldOutcome::Dislodged(by)) => write!(f, "Dislodged by {}", by),
+            OrderOutcome::Move(outcome) => match outcome {
+                AttackOutcome::MoveToSelf => write!(f, "Fails: move to own region"),
+                AttackOutcome::NoPath => write!(f, "Fails: no path"),
+                AttackOutcome::ConvoyDisrupted => write!(f, "Fails: convoy disrupted"),
+                AttackOutcome::FriendlyFire => write!(f, "Fails: friendly fire"),
+                AttackOutcome::Prevented(by) => write!(f, "Prevented by {}", by),
+                AttackOutcome::LostHeadToHead(to) => write!(f, "Lost head-to-head to {}", to),
+                AttackOutcome::OccupierDefended => write!(f, "Fails: occupier defended"),
+                AttackOutcome::Succeeds => write!(f, "Succeeds"),
+            },
+            OrderOutcome::Support(outcome) => match outcome {
+                SupportOutcome::NotDisrupted => write!(f, "Succeeds"),
+                SupportOutcome::SupportingSelf => write!(f, "Fails: supporting self"),
+                SupportOutcome::CantReach => write!(f, "Fails: can't reach"),
+                SupportOutcome::CutBy(by) => write!(f, "Cut by {}", by),
+            },
+            OrderOutcome::Convoy(outcome) => match outcome {
+                ConvoyOutcome::NotAtSea => write!(f, "Fails: not at sea"),
+                ConvoyOutcome::Dislodged(by) => write!(f, "Dislodged by {}", by),
+                ConvoyOutcome::Paradox => write!(f, "Fails: paradox"),
+                ConvoyOutcome::NotDisrupted => write!(f, "Succeeds"),
+                ConvoyOutcome::SurvivedAttack => write!(f, "Succeeds: survived attack"),
+            },
+        }
+    }
+}
+
 /// Outcome for an order that was illegal and not considered during adjudication.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -107,11 +178,45 @@ impl<'a, A: Adjudicate> Outcome<'a, A> {
             )
             .collect();
 
-        Self {
+        let outcome = Self {
             context,
             resolver,
             orders,
+        };
+
+        #[cfg(all(feature = "check-invariants", debug_assertions))]
+        if let Err(violations) = outcome.check_invariants() {
+            panic!("Adjudication violated invariants: {violations:#?}");
         }
+
+        outcome
+    }
+
+    /// The rules used to adjudicate the turn.
+    pub fn rules(&self) -> &A {
+        &self.context.rules
+    }
+
+    /// The supply center ownership at the start of the turn, if it was provided to the
+    /// [`Submission`](super::Submission).
+    pub fn ownerships(&self) -> Option<&HashMap<ProvinceKey, Nation>> {
+        self.context.ownerships
+    }
+
+    /// The supply center ownership after this turn, if this is a fall turn, or `None` if
+    /// ownership was not provided to the [`Submission`](super::Submission).
+    ///
+    /// Each unit in its [resulting position](Outcome::resulting_positions) captures the supply
+    /// center it occupies. Dislodged units capture nothing; if the turn dislodged any units,
+    /// use [`build::capture_supply_centers`](super::build::capture_supply_centers) with the
+    /// retreat phase's outcome instead, since a unit may retreat into a supply center.
+    pub fn ownerships_after_fall(&self) -> Option<HashMap<ProvinceKey, Nation>> {
+        let ownerships = self.context.ownerships?;
+        Some(build::capture_supply_centers(
+            self.context.world_map,
+            ownerships,
+            &self.resulting_positions(),
+        ))
     }
 
     /// The orders that participated in resolution, in the order they were provided. This does not
@@ -122,21 +227,589 @@ impl<'a, A: Adjudicate> Outcome<'a, A> {
 
     /// The union of all orders known to the outcome. This will include any illegal orders and the hold
     /// orders generated to ensure all units had an order during adjudication.
+    ///
+    /// If the submission used [`IllegalOrderPolicy::Preserve`](super::IllegalOrderPolicy::Preserve),
+    /// holds generated for units whose orders were illegal are excluded.
     pub fn all_orders(&self) -> impl Iterator<Item = &MappedMainOrder> {
-        self.orders.keys().copied()
+        self.all_orders_with_outcomes().map(|(ord, _)| ord)
     }
 
+    /// Every order known to the outcome with its outcome, as in [`Outcome::all_orders`].
+    ///
+    /// This includes a hold order for every unit that was not ordered, so that each unit on
+    /// the board appears exactly once alongside any illegal orders it received. Use
+    /// [`Outcome::generated_hold_orders`] to tell those holds apart from submitted orders.
     pub fn all_orders_with_outcomes(
         &self,
-    ) -> impl Iterator<Item = (&MappedMainOrder, &OrderOutcome<&MappedMainOrder>)> {
-        self.orders.iter().map(|(ord, outcome)| (*ord, outcome))
+    ) -> impl Iterator<Item = (&MappedMainOrder, &OrderOutcome<&'a MappedMainOrder>)> {
+        self.orders
+            .iter()
+            .filter(|(ord, _)| !self.context.hidden_orders.contains(*ord))
+            .map(|(ord, outcome)| (*ord, outcome))
+    }
+
+    /// The hold orders generated for units that had no order or only illegal orders, in the
+    /// order those units were found. These were not submitted by a player.
+    ///
+    /// Holds excluded from [`Outcome::all_orders`] by
+    /// [`IllegalOrderPolicy::Preserve`](super::IllegalOrderPolicy::Preserve) are excluded
+    /// here as well.
+    pub fn generated_hold_orders(&self) -> impl Iterator<Item = &MappedMainOrder> {
+        self.context.orders().filter(|ord| {
+            self.context.generated_orders.contains(ord) && !self.context.hidden_orders.contains(ord)
+        })
+    }
+
+    pub fn get(&self, order: &MappedMainOrder) -> Option<&OrderOutcome<&'a MappedMainOrder>> {
+        if self.context.hidden_orders.contains(order) {
+            None
+        } else {
+            self.orders.get(order)
+        }
+    }
+
+    /// Count the successful moves, bounces, dislodgements, cut supports, and disrupted convoys
+    /// in the turn. Illegal orders are not counted.
+    pub fn summary(&self) -> TurnSummary {
+        let mut summary = TurnSummary {
+            dislodgements: self.dislodged_positions().len(),
+            ..TurnSummary::default()
+        };
+
+        for (_, outcome) in self.all_orders_with_outcomes() {
+            match outcome {
+                OrderOutcome::Move(AttackOutcome::Succeeds) => summary.successful_moves += 1,
+                OrderOutcome::Move(
+                    AttackOutcome::Prevented(_)
+                    | AttackOutcome::OccupierDefended
+                    | AttackOutcome::LostHeadToHead(_)
+                    | AttackOutcome::FriendlyFire,
+                ) => summary.bounces += 1,
+                OrderOutcome::Support(SupportOutcome::CutBy(_)) => summary.cut_supports += 1,
+                OrderOutcome::Convoy(ConvoyOutcome::Dislodged(_) | ConvoyOutcome::Paradox) => {
+                    summary.disrupted_convoys += 1
+                }
+                _ => {}
+            }
+        }
+
+        summary
+    }
+
+    /// Compare the outcome to a set of expected order states, returning the orders whose actual
+    /// state differed from the expected state, sorted by order.
+    ///
+    /// Orders in the outcome that are not in `expected` are ignored.
+    #[allow(clippy::implicit_hasher)]
+    pub fn diff<'b>(
+        &'b self,
+        expected: &'b HashMap<MappedMainOrder, OrderState>,
+    ) -> Vec<OutcomeDiff<'b>> {
+        let mut diffs = expected
+            .iter()
+            .filter_map(|(order, &expected)| {
+                let explanation = self
+                    .orders
+                    .get(order)
+                    .filter(|_| !self.context.hidden_orders.contains(order));
+                let actual = explanation.map(OrderState::from);
+                if actual == Some(expected) {
+                    None
+                } else {
+                    Some(OutcomeDiff {
+                        order,
+                        expected,
+                        actual,
+                        explanation,
+                    })
+                }
+            })
+            .collect::<Vec<_>>();
+        diffs.sort_by_key(|diff| diff.order);
+        diffs
+    }
+
+    /// A submission of the orders that participated in this resolution, with `removed` replaced
+    /// by a hold, to find out whether the outcome depends on that order. Adjudicate it to get
+    /// the counterfactual outcome, which is a new resolution of every order.
+    ///
+    /// This returns a [`Submission`] rather than an `Outcome`, since an outcome borrows the
+    /// orders it resolved; use [`counterfactual_state`](Outcome::counterfactual_state) to get
+    /// the state of a single order directly. Illegal orders are left out, as they did not
+    /// affect the outcome. If `removed` did not participate in resolution, the orders are
+    /// unchanged.
+    pub fn counterfactual(&self, removed: &MappedMainOrder) -> Submission<'a> {
+        let orders = self
+            .context
+            .orders()
+            .map(|ord| {
+                if ord == removed {
+                    Order::new(
+                        ord.nation.clone(),
+                        ord.unit_type,
+                        ord.region.clone(),
+                        MainCommand::Hold,
+                    )
+                } else {
+                    ord.clone()
+                }
+            })
+            .collect::<Vec<_>>();
+
+        match self.context.ownerships {
+            Some(ownerships) => Submission::with_state(
+                self.context.world_map,
+                &orders,
+                ownerships.clone(),
+                orders.clone(),
+            ),
+            None => Submission::with_inferred_state(self.context.world_map, orders),
+        }
+    }
+
+    /// Whether `order` would have succeeded under `rules` if `removed` had been replaced by a
+    /// hold, or `None` if `order` is not in the [counterfactual](Outcome::counterfactual)
+    /// submission.
+    pub fn counterfactual_state(
+        &self,
+        removed: &MappedMainOrder,
+        order: &MappedMainOrder,
+        rules: impl Adjudicate,
+    ) -> Option<OrderState> {
+        let submission = self.counterfactual(removed);
+        let outcome = submission.adjudicate(rules);
+        let state = outcome.get(order).map(OrderState::from);
+        state
+    }
+
+    /// The support orders which counted toward the strength of `order` in the final resolution,
+    /// in the order they were submitted.
+    ///
+    /// Supports which were cut or otherwise disrupted are excluded. For moves, supports given by
+    /// the nation of a unit resisting the move are also excluded, as a nation cannot help
+    /// dislodge its own unit unless the rules allow [self-dislodgement](SelfDislodgement).
+    pub fn supports_for(&'a self, order: &MappedMainOrder) -> Vec<&'a MappedMainOrder> {
+        let ctx = &self.context;
+        let mut state = self.resolver.clone();
+        let mut supports = support::find_for(ctx, &mut state, order);
+
+        if let Some(dest) = order.move_dest() {
+            if let Some(occupier) = ctx.find_order_to_province(dest.province()) {
+                let resists = !occupier.is_move()
+                    || calc::is_head_to_head(ctx, &mut state, order, occupier)
+                    || state.resolve(ctx, occupier) == OrderState::Fails;
+
+                if resists
+                    && ctx.rules.policies().self_dislodgement == SelfDislodgement::Prohibited
+                    && occupier.nation != order.nation
+                {
+                    supports.retain(|sup| sup.nation != occupier.nation);
+                }
+            }
+        }
+
+        supports
+    }
+
+    /// The supports for `order` which were cut, each paired with the order that cut it, in the
+    /// order they were submitted.
+    ///
+    /// This is the inverse of [`SupportOutcome::CutBy`], and explains why an order had less
+    /// strength than the supports ordered for it.
+    pub fn cut_supports_for(
+        &self,
+        order: &MappedMainOrder,
+    ) -> Vec<(&MappedMainOrder, &MappedMainOrder)> {
+        self.context
+            .orders()
+            .filter(|sup| support::is_support_for(&self.context, sup, order))
+            .filter_map(|sup| match self.get(sup) {
+                Some(OrderOutcome::Support(SupportOutcome::CutBy(cutter))) => Some((sup, *cutter)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The supports which counted toward a successful order but were not needed, in the order
+    /// they were submitted.
+    ///
+    /// A support is redundant if replacing it with a hold leaves the state of every other order
+    /// unchanged, which is found by adjudicating the [counterfactual](Outcome::counterfactual)
+    /// for each support. Each support is considered on its own, so two supports may both be
+    /// redundant even though the order needed one of them. Cut supports are never redundant,
+    /// as they did not count toward anything.
+    pub fn redundant_supports(&'a self) -> Vec<&'a MappedMainOrder> {
+        let mut redundant = self
+            .context
+            .orders()
+            .filter(|ord| self.get(ord).is_some_and(OrderOutcome::is_successful))
+            .flat_map(|ord| self.supports_for(ord))
+            .filter(|sup| {
+                let submission = self.counterfactual(sup);
+                let mut expected = HashMap::from(submission.adjudicate(self.rules()));
+                expected.retain(|ord, _| ord.region != sup.region);
+                self.diff(&expected).is_empty()
+            })
+            .collect::<Vec<_>>();
+
+        let submitted = self.context.orders().collect::<Vec<_>>();
+        redundant.sort_by_key(|sup| submitted.iter().position(|ord| ord == sup));
+        redundant.dedup();
+        redundant
     }
 
-    pub fn get(
-        &'a self,
-        order: &'a MappedMainOrder,
-    ) -> Option<&'a OrderOutcome<&'a MappedMainOrder>> {
-        self.orders.get(order)
+    /// How the unit ordered by `order` travelled to its destination, or `None` if the order is
+    /// not a legal move or there was no path to its destination.
+    ///
+    /// This distinguishes armies moving to an adjacent province that were carried by a convoy
+    /// from those that moved over land, such as when the convoy was disrupted.
+    pub fn move_route(&self, order: &MappedMainOrder) -> Option<MoveRoute> {
+        if !order.is_move() || self.context.illegal_orders.contains_key(order) {
+            return None;
+        }
+
+        convoy::move_route(&self.context, &mut self.resolver.clone(), order)
+    }
+
+    /// The strength each unit brought to bear on `province`, for explaining why a contested
+    /// province was or wasn't entered.
+    ///
+    /// The unit in the province, if any, is listed first with the strength it resisted with:
+    /// its hold strength if it stayed, its defend strength if it lost or won a head-to-head
+    /// battle, 1 if it otherwise failed to leave, or 0 if it left. A unit that resisted also
+    /// gets any [defense bonus](Adjudicate::defense_bonus). Each unit that tried to move into
+    /// the province follows in the order it was submitted, with its attack strength. Attack
+    /// strength excludes supports from the nation of a resisting unit, as well as cut
+    /// supports. A move with no path to the province, or which would dislodge a unit of its
+    /// own nation, has a strength of 0.
+    pub fn contest_report(&'a self, province: &ProvinceKey) -> Vec<(Nation, u32)> {
+        let ctx = &self.context;
+        let mut state = self.resolver.clone();
+        let mut report = vec![];
+
+        let occupier = ctx.find_order_to_province(province);
+        let mut resisting_nation = None;
+        if let Some(occupier) = occupier {
+            let in_head_to_head = occupier.is_move()
+                && ctx.orders().any(|ord| {
+                    ord.is_move_to_province(province)
+                        && calc::is_head_to_head(ctx, &mut state, ord, occupier)
+                });
+
+            let strength = if !occupier.is_move() || in_head_to_head {
+                1 + support::find_for(ctx, &mut state, occupier).len()
+                    + ctx.rules.defense_bonus(occupier)
+            } else if state.resolve(ctx, occupier) == OrderState::Fails {
+                1 + ctx.rules.defense_bonus(occupier)
+            } else {
+                0
+            };
+
+            if strength > 0 {
+                resisting_nation = Some(&occupier.nation);
+            }
+            report.push((occupier.nation.clone(), strength as u32));
+        }
+
+        let self_dislodgement_prohibited =
+            ctx.rules.policies().self_dislodgement == SelfDislodgement::Prohibited;
+        for attacker in ctx.orders().filter(|ord| ord.is_move_to_province(province)) {
+            let friendly_fire =
+                self_dislodgement_prohibited && resisting_nation == Some(&attacker.nation);
+            let strength = if !friendly_fire && calc::path_exists(ctx, &mut state, attacker) {
+                1 + self.supports_for(attacker).len()
+            } else {
+                0
+            };
+            report.push((attacker.nation.clone(), strength as u32));
+        }
+
+        report
+    }
+
+    /// The convoy orders which were treated as holds to break a paradox, in the order they were
+    /// provided.
+    ///
+    /// Explaining these to players is useful for counterintuitive results, such as
+    /// [Pandin's Paradox](https://webdiplomacy.net/doc/DATC_v3_0.html#6.F.16).
+    pub fn paradoxical_orders(&self) -> impl Iterator<Item = &MappedMainOrder> {
+        self.context
+            .orders()
+            .filter(move |ord| self.resolver.order_in_paradox(ord))
+    }
+
+    /// The legal orders from other nations which targeted a unit or owned supply center of
+    /// `nation`, in the order they were provided.
+    ///
+    /// An order targets a province if it moves there or supports or convoys a move there; see
+    /// [`MainCommand::targets_province`]. Supply centers are only considered if the
+    /// submission included ownerships.
+    pub fn attacks_on(&self, nation: &Nation) -> Vec<&MappedMainOrder> {
+        self.context
+            .orders()
+            .filter(|ord| ord.nation != *nation)
+            .filter(|ord| {
+                ord.command.targets_province().is_some_and(|province| {
+                    self.context
+                        .find_order_to_province(province)
+                        .is_some_and(|occupier| occupier.nation == *nation)
+                        || self
+                            .context
+                            .ownerships
+                            .and_then(|owners| owners.get(province))
+                            == Some(nation)
+                })
+            })
+            .collect()
+    }
+
+    /// The legal support orders in the turn, with what each was trying to support and
+    /// whether it was disrupted. Order is unspecified.
+    ///
+    /// This is intended for drawing support arcs, where hold and move supports are shown
+    /// differently.
+    pub fn supports(
+        &self,
+    ) -> impl Iterator<
+        Item = (
+            &MappedMainOrder,
+            SupportKind,
+            &SupportOutcome<&MappedMainOrder>,
+        ),
+    > {
+        self.all_orders_with_outcomes()
+            .filter_map(|(ord, outcome)| match (&ord.command, outcome) {
+                (MainCommand::Support(supported), OrderOutcome::Support(support_outcome)) => {
+                    Some((ord, SupportKind::from(supported), support_outcome))
+                }
+                _ => None,
+            })
+    }
+
+    /// The position of every unit that was not dislodged at the end of the main phase, sorted
+    /// by region.
+    ///
+    /// Units whose moves succeeded are in their destinations, and all other units are where
+    /// they started. Dislodged units are reported by [`Outcome::dislodged_positions`].
+    pub fn resulting_positions(&self) -> Vec<UnitPosition<'static, RegionKey>> {
+        let mut state = self.resolver.clone();
+        let mut positions = self
+            .context
+            .orders()
+            .filter(|ord| calc::dislodger_of(&self.context, &mut state, ord).is_none())
+            .map(|ord| {
+                let region = match self.orders.get(ord) {
+                    Some(OrderOutcome::Move(AttackOutcome::Succeeds)) => ord
+                        .move_dest()
+                        .expect("Successful move should have destination"),
+                    _ => &ord.region,
+                };
+
+                UnitPosition::new(Unit::from(ord), region).to_owned()
+            })
+            .collect::<Vec<_>>();
+        positions.sort_by(|a, b| a.region.cmp(&b.region));
+        positions
+    }
+
+    /// The starting position of every unit dislodged during the main phase, sorted by region.
+    /// These units must retreat or disband before the next main phase.
+    pub fn dislodged_positions(&self) -> Vec<UnitPosition<'static, RegionKey>> {
+        let mut state = self.resolver.clone();
+        let mut positions = self
+            .context
+            .orders()
+            .filter(|ord| calc::dislodger_of(&self.context, &mut state, ord).is_some())
+            .map(|ord| UnitPosition::from(ord).to_owned())
+            .collect::<Vec<_>>();
+        positions.sort_by(|a, b| a.region.cmp(&b.region));
+        positions
+    }
+
+    /// Chains of moves which succeeded by rotating units around a circle, such as three armies
+    /// each moving into the next one's province (DATC 6.C.1), or two units swapping places
+    /// with the help of a convoy.
+    ///
+    /// Each chain starts with its least order and follows the moves around the circle. Chains
+    /// are sorted by their first order.
+    pub fn circular_move_chains(&self) -> Vec<Vec<&'a MappedMainOrder>> {
+        self.move_circles()
+            .into_iter()
+            .filter(|circle| {
+                circle
+                    .iter()
+                    .all(|ord| self.get(ord).is_some_and(OrderOutcome::is_successful))
+            })
+            .collect()
+    }
+
+    /// Every circle of move orders, whether or not the moves succeeded, each starting with its
+    /// least order.
+    fn move_circles(&self) -> Vec<Vec<&'a MappedMainOrder>> {
+        let moves = self
+            .context
+            .orders()
+            .filter(|ord| ord.is_move())
+            .map(|ord| (ord.region.province(), ord))
+            .collect::<HashMap<_, _>>();
+
+        let mut circles = vec![];
+        for start in self.context.orders().filter(|ord| ord.is_move()) {
+            let mut circle = vec![start];
+            let is_circle = loop {
+                let next = circle
+                    .last()
+                    .and_then(|ord| ord.move_dest())
+                    .and_then(|dest| moves.get(dest.province()));
+                match next {
+                    Some(&next) if next == start => break true,
+                    Some(&next) if !circle.contains(&next) => circle.push(next),
+                    _ => break false,
+                }
+            };
+
+            // Every member of a circle leads back to it, so only keep it from its least member.
+            if is_circle && circle.len() > 1 && circle.iter().all(|ord| *ord >= start) {
+                circles.push(circle);
+            }
+        }
+
+        circles.sort();
+        circles
+    }
+
+    /// Check properties that every correct adjudication has, returning a description of each
+    /// violation. A violation indicates a bug in the adjudicator, not in the orders.
+    ///
+    /// The properties are:
+    ///
+    /// 1. No two units end the phase in the same province.
+    /// 1. Every dislodged unit's province is entered by a successful move.
+    /// 1. The moves in a circular movement of three or more units succeed or fail together,
+    ///    unless a unit outside the circle tries to enter one of its provinces.
+    ///
+    /// This is meant for tests, and is much slower than adjudication itself. With the
+    /// `check-invariants` feature, debug builds run it after every adjudication and panic
+    /// on a violation.
+    pub fn check_invariants(&self) -> Result<(), Vec<String>> {
+        let mut violations = vec![];
+
+        let mut occupied = HashMap::<ProvinceKey, usize>::default();
+        for pos in self.resulting_positions() {
+            *occupied.entry(pos.region.province().clone()).or_default() += 1;
+        }
+        let mut crowded = occupied
+            .into_iter()
+            .filter(|(_, units)| *units > 1)
+            .collect::<Vec<_>>();
+        crowded.sort();
+        for (province, units) in crowded {
+            violations.push(format!("{} units end in {}", units, province.short_name()));
+        }
+
+        for pos in self.dislodged_positions() {
+            let province = pos.region.province();
+            let entered = self.context.orders().any(|ord| {
+                ord.is_move_to_province(province)
+                    && self.get(ord).is_some_and(OrderOutcome::is_successful)
+            });
+            if !entered {
+                violations.push(format!(
+                    "unit in {} was dislodged, but no unit entered {}",
+                    pos.region,
+                    province.short_name()
+                ));
+            }
+        }
+
+        for circle in self.move_circles() {
+            if circle.len() < 3 {
+                continue;
+            }
+
+            let contested = self.context.orders().any(|ord| {
+                !circle.contains(&ord)
+                    && circle
+                        .iter()
+                        .any(|member| ord.is_move_to_province(member.region.province()))
+            });
+            let states = circle
+                .iter()
+                .filter_map(|ord| self.get(ord).map(OrderState::from))
+                .collect::<Vec<_>>();
+            if !contested && states.windows(2).any(|pair| pair[0] != pair[1]) {
+                let members = circle.iter().map(|ord| ord.to_string()).collect::<Vec<_>>();
+                violations.push(format!(
+                    "circular movement partly succeeded: {}",
+                    members.join(", ")
+                ));
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// The visible changes in the turn, in the order a renderer would animate them.
+    ///
+    /// Moves and bounces come first, in the order their orders were provided, followed by
+    /// dislodgements sorted by region. Units that held, supported, or convoyed without being
+    /// dislodged produce no events.
+    pub fn to_events(&self) -> Vec<TurnEvent> {
+        let mut state = self.resolver.clone();
+        let mut events = vec![];
+
+        for ord in self.context.orders() {
+            let (Some(to), Some(outcome)) = (ord.move_dest(), self.orders.get(ord)) else {
+                continue;
+            };
+
+            let unit = UnitPosition::from(ord).to_owned();
+
+            match outcome {
+                OrderOutcome::Move(AttackOutcome::Succeeds) => {
+                    let route = match convoy::move_route(&self.context, &mut state, ord) {
+                        Some(MoveRoute::Convoyed) => convoy::routes(&self.context, &mut state, ord)
+                            .ok()
+                            .and_then(|routes| routes.into_iter().next()),
+                        _ => None,
+                    };
+
+                    events.push(match route {
+                        Some(route) => TurnEvent::ConvoyedMove {
+                            unit,
+                            to: to.clone(),
+                            route: route.into_iter().map(|f| f.region.clone()).collect(),
+                        },
+                        None => TurnEvent::Move {
+                            unit,
+                            to: to.clone(),
+                        },
+                    });
+                }
+                OrderOutcome::Move(
+                    AttackOutcome::Prevented(_)
+                    | AttackOutcome::OccupierDefended
+                    | AttackOutcome::LostHeadToHead(_)
+                    | AttackOutcome::FriendlyFire,
+                ) => events.push(TurnEvent::Bounce {
+                    unit,
+                    to: to.clone(),
+                }),
+                _ => {}
+            }
+        }
+
+        events.extend(
+            self.dislodged_positions()
+                .into_iter()
+                .map(|unit| TurnEvent::Dislodged { unit }),
+        );
+
+        events
     }
 
     /// Calculate retreat phase starting data based on this main-phase outcome.
@@ -144,9 +817,34 @@ impl<'a, A: Adjudicate> Outcome<'a, A> {
         retreat::Start::new(self)
     }
 
+    /// Get the regions to which each dislodged unit may retreat, sorted by region.
+    ///
+    /// Destinations which are unreachable, occupied, contested, or blocked by the unit's
+    /// dislodger are excluded. A dislodged unit with no valid destinations is included with
+    /// an empty list, as it must disband.
+    pub fn retreat_options(&'a self) -> HashMap<UnitPosition<'a>, Vec<RegionKey>> {
+        self.to_retreat_start()
+            .retreat_destinations()
+            .iter()
+            .map(|(unit, dests)| {
+                (
+                    unit.clone(),
+                    dests.available().into_iter().cloned().collect(),
+                )
+            })
+            .collect()
+    }
+
+    /// Counts of the work done to resolve the turn, for finding positions that are expensive
+    /// to adjudicate.
+    #[cfg(feature = "resolution-stats")]
+    pub fn resolution_stats(&self) -> &ResolutionStats<'a> {
+        self.resolver.stats()
+    }
+
     #[cfg(feature = "dependency-graph")]
     pub fn dependencies(&self) -> impl fmt::Display {
-        struct Dependencies(std::collections::BTreeSet<(MappedMainOrder, MappedMainOrder)>);
+        struct Dependencies(alloc::collections::BTreeSet<(MappedMainOrder, MappedMainOrder)>);
 
         impl fmt::Display for Dependencies {
             fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -163,6 +861,93 @@ impl<'a, A: Adjudicate> Outcome<'a, A> {
     }
 }
 
+impl<'a, A: Adjudicate> PhaseOutcome for Outcome<'a, A> {
+    type Order = MappedMainOrder;
+    type OrderOutcome = OrderOutcome<&'a MappedMainOrder>;
+
+    fn get(&self, order: &MappedMainOrder) -> Option<&Self::OrderOutcome> {
+        Outcome::get(self, order)
+    }
+
+    fn order_outcomes(&self) -> impl Iterator<Item = (&MappedMainOrder, &Self::OrderOutcome)> {
+        self.all_orders_with_outcomes()
+    }
+}
+
+/// Counts of notable events in a turn, produced by [`Outcome::summary`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TurnSummary {
+    /// Moves that reached their destination.
+    pub successful_moves: usize,
+    /// Moves that had a path to their destination but were stopped by another unit, whether
+    /// that unit was foreign or belonged to the moving unit's own nation.
+    ///
+    /// Moves with no path and moves to the unit's own region are not bounces.
+    pub bounces: usize,
+    /// Units that were dislodged and must retreat or disband.
+    pub dislodgements: usize,
+    /// Support orders that were cut.
+    pub cut_supports: usize,
+    /// Convoy orders that were disrupted, whether by dislodgement or to resolve a paradox.
+    pub disrupted_convoys: usize,
+}
+
+/// A visible change in a turn, produced by [`Outcome::to_events`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+pub enum TurnEvent {
+    /// A unit moved across a border to `to`.
+    Move {
+        unit: UnitPosition<'static, RegionKey>,
+        to: RegionKey,
+    },
+    /// An army was carried to `to` by the fleets in `route`, listed from the army's
+    /// origin to its destination.
+    ConvoyedMove {
+        unit: UnitPosition<'static, RegionKey>,
+        to: RegionKey,
+        route: Vec<RegionKey>,
+    },
+    /// A unit tried to move to `to` but was stopped by another unit, and stayed in place.
+    Bounce {
+        unit: UnitPosition<'static, RegionKey>,
+        to: RegionKey,
+    },
+    /// A unit was dislodged and must retreat or disband.
+    Dislodged {
+        unit: UnitPosition<'static, RegionKey>,
+    },
+}
+
+/// A difference between the expected and actual state of an order, produced by
+/// [`Outcome::diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutcomeDiff<'a> {
+    /// The order whose state differed.
+    pub order: &'a MappedMainOrder,
+    /// The state the order was expected to have.
+    pub expected: OrderState,
+    /// The state the order actually had, or `None` if the order was not in the outcome.
+    pub actual: Option<OrderState>,
+    /// Why the order had its actual state, or `None` if the order was not in the outcome.
+    pub explanation: Option<&'a OrderOutcome<&'a MappedMainOrder>>,
+}
+
+impl fmt::Display for OutcomeDiff<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.explanation {
+            Some(explanation) => write!(
+                f,
+                "{}: expected {:?}, got {}",
+                self.order, self.expected, explanation
+            ),
+            None => write!(f, "{}: not in outcome", self.order),
+        }
+    }
+}
+
 #[allow(clippy::implicit_hasher)]
 impl<A: Adjudicate> From<Outcome<'_, A>> for HashMap<MappedMainOrder, OrderState> {
     fn from(other: Outcome<'_, A>) -> Self {
@@ -0,0 +1,18 @@
+//! A common interface to the outcomes of each phase of a turn.
+
+/// The result of adjudicating one phase of a turn, whether main, retreat, or build.
+///
+/// Each phase has its own order and outcome types; this trait lets tools that report on
+/// adjudication handle any phase with the same code.
+pub trait PhaseOutcome {
+    /// The type of order adjudicated in the phase.
+    type Order;
+    /// The outcome of a single order in the phase.
+    type OrderOutcome;
+
+    /// Get the outcome of an order, or `None` if the order was not part of the phase.
+    fn get(&self, order: &Self::Order) -> Option<&Self::OrderOutcome>;
+
+    /// Iterate over every order in the phase with its outcome, in unspecified order.
+    fn order_outcomes(&self) -> impl Iterator<Item = (&Self::Order, &Self::OrderOutcome)>;
+}
@@ -1,10 +1,30 @@
-use super::{convoy, Adjudicate, IllegalOrder, MappedMainOrder, OrderState, Outcome, Rulebook};
-use crate::geo::{Map, ProvinceKey, RegionKey};
-use crate::order::{Command, MainCommand, Order};
-use crate::{Unit, UnitPosition, UnitPositions};
-use std::collections::{HashMap, HashSet};
+use super::{
+    convoy, retreat, Adjudicate, IllegalOrder, MappedMainOrder, OrderState, Outcome, Rulebook,
+};
+use crate::collections::{HashMap, HashSet};
+use crate::geo::{Map, ProvinceKey, RegionKey, Terrain};
+use crate::order::{Command, MainCommand, Order, RetreatCommand, SupportedOrder};
+use crate::{Nation, Unit, UnitPosition, UnitPositions, UnitType};
+use alloc::borrow::Cow;
 #[cfg(feature = "dependency-graph")]
-use std::{cell::RefCell, collections::BTreeSet, rc::Rc};
+use alloc::{collections::BTreeSet, rc::Rc};
+use alloc::{string::ToString, vec, vec::Vec};
+#[cfg(feature = "dependency-graph")]
+use core::cell::RefCell;
+
+/// How a [`Submission`] reports units whose submitted orders were all illegal.
+///
+/// Such units always hold during adjudication; this only controls what the outcome reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IllegalOrderPolicy {
+    /// Report a generated hold order for the unit alongside its illegal orders.
+    #[default]
+    SubstituteHold,
+    /// Report only the orders the player submitted, so the unit's illegal orders stand in for
+    /// the hold the unit was adjudicated as making.
+    Preserve,
+}
 
 /// A list of orders submitted for adjudication against a given world map.
 ///
@@ -18,6 +38,9 @@ pub struct Submission<'a> {
     /// A map of indexes in `submitted_orders` to the reason those orders are illegal.
     // This uses indices because Rust doesn't support self-referential structs.
     illegal_orders: HashMap<usize, IllegalOrder>,
+    /// Supply center ownership at the start of the turn, if known.
+    ownerships: Option<HashMap<ProvinceKey, Nation>>,
+    illegal_order_policy: IllegalOrderPolicy,
 }
 
 impl<'a> Submission<'a> {
@@ -39,6 +62,23 @@ impl<'a> Submission<'a> {
         Submission::new_internal(world_map, None::<&Vec<MappedMainOrder>>, orders)
     }
 
+    /// Start a new adjudication by submitting orders against a given starting state, including
+    /// the supply center ownership at the start of the turn.
+    ///
+    /// Ownership does not affect the outcome of orders, but it is carried through to the
+    /// [`Outcome`], which can [apply captures](Outcome::ownerships_after_fall) after a fall
+    /// turn to find the ownership on the board after the turn.
+    pub fn with_state(
+        world_map: &'a Map,
+        starting_state: &impl UnitPositions<RegionKey>,
+        ownerships: HashMap<ProvinceKey, Nation>,
+        orders: Vec<MappedMainOrder>,
+    ) -> Self {
+        let mut submission = Submission::new_internal(world_map, Some(starting_state), orders);
+        submission.ownerships = Some(ownerships);
+        submission
+    }
+
     fn new_internal(
         world_map: &'a Map,
         start: Option<&impl UnitPositions<RegionKey>>,
@@ -48,7 +88,9 @@ impl<'a> Submission<'a> {
             world_map,
             submitted_orders: orders,
             civil_disorder_orders: vec![],
-            illegal_orders: HashMap::new(),
+            illegal_orders: HashMap::default(),
+            ownerships: None,
+            illegal_order_policy: IllegalOrderPolicy::default(),
         };
 
         let (illegal_orders, missing_orders) = if let Some(start) = start {
@@ -62,8 +104,73 @@ impl<'a> Submission<'a> {
         temp
     }
 
+    /// Set how the outcome reports units whose orders were all illegal.
+    pub fn with_illegal_order_policy(mut self, policy: IllegalOrderPolicy) -> Self {
+        self.illegal_order_policy = policy;
+        self
+    }
+
     /// Adjudicate the submission using the provided rules.
-    pub fn adjudicate<A: Adjudicate>(&self, rules: A) -> Outcome<A> {
+    pub fn adjudicate<A: Adjudicate>(&self, rules: A) -> Outcome<'_, A> {
+        self.to_context(rules).resolve()
+    }
+
+    /// Adjudicate the submission using the provided rules, then resolve the retreat phase that
+    /// follows without player input.
+    ///
+    /// Each dislodged unit retreats to the first of its available destinations in region
+    /// order, and disbands if it has none. Units whose retreats conflict disband, as usual.
+    pub fn adjudicate_through_retreats<A: Adjudicate>(
+        &self,
+        rules: A,
+    ) -> (Outcome<'_, A>, retreat::Report) {
+        let outcome = self.adjudicate(rules);
+        let report = {
+            let start = outcome.to_retreat_start();
+            let orders = start
+                .retreat_destinations()
+                .iter()
+                .map(|(pos, dests)| {
+                    let command = match dests.available().into_iter().next() {
+                        Some(dest) => RetreatCommand::Move(dest.clone()),
+                        None => RetreatCommand::Disband,
+                    };
+
+                    Order::new(
+                        pos.nation().clone(),
+                        pos.unit.unit_type(),
+                        pos.region.clone(),
+                        command,
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            let context = retreat::Context::new(&start, orders);
+            let retreat_outcome = context.resolve();
+            retreat_outcome.to_report()
+        };
+
+        (outcome, report)
+    }
+
+    /// Adjudicate the submission using the provided rules, resolving each group of
+    /// [independent orders](Context::clusters) separately. Under
+    /// [`Rulebook`](super::Rulebook), the outcome is the same as that of
+    /// [`Submission::adjudicate`]; see [`Context::resolve_partitioned`].
+    pub fn adjudicate_partitioned<A: Adjudicate>(&self, rules: A) -> Outcome<'_, A> {
+        self.to_context(rules).resolve_partitioned()
+    }
+
+    /// Adjudicate the submission using the provided rules, resolving groups of
+    /// [independent orders](Context::clusters) on a pool of threads when there are enough
+    /// orders to benefit. Under [`Rulebook`](super::Rulebook), the outcome is the same as that
+    /// of [`Submission::adjudicate`]; see [`Context::resolve_parallel`].
+    #[cfg(feature = "parallel")]
+    pub fn adjudicate_parallel<A: Adjudicate + Sync>(&self, rules: A) -> Outcome<'_, A> {
+        self.to_context(rules).resolve_parallel()
+    }
+
+    fn to_context<A: Adjudicate>(&self, rules: A) -> Context<'_, A> {
         let illegal_orders = self
             .illegal_orders
             .iter()
@@ -80,8 +187,28 @@ impl<'a> Submission<'a> {
         );
 
         context.illegal_orders = illegal_orders;
+        context.ownerships = self.ownerships.as_ref();
+        context.generated_orders = self.civil_disorder_orders.iter().collect();
+        if self.illegal_order_policy == IllegalOrderPolicy::Preserve {
+            let substitutes = self
+                .civil_disorder_orders
+                .iter()
+                .filter(|hold| {
+                    context
+                        .illegal_orders
+                        .keys()
+                        .any(|ord| ord.unit_position() == hold.unit_position())
+                })
+                .collect();
+            context.hidden_orders = substitutes;
+        }
 
-        context.resolve()
+        context
+    }
+
+    /// The supply center ownership at the start of the turn, if it was provided.
+    pub fn ownerships(&self) -> Option<&HashMap<ProvinceKey, Nation>> {
+        self.ownerships.as_ref()
     }
 
     /// The exact orders that were provided at submission time, including illegal orders and
@@ -90,6 +217,16 @@ impl<'a> Submission<'a> {
         self.submitted_orders.iter()
     }
 
+    /// The submitted orders in canonical order: by nation, then unit, then command.
+    ///
+    /// Unlike [`Submission::submitted_orders`], the result does not depend on the order in
+    /// which orders were submitted. See [`Order`] for details of the ordering.
+    pub fn sorted_orders(&self) -> Vec<&MappedMainOrder> {
+        let mut orders = self.submitted_orders().collect::<Vec<_>>();
+        orders.sort();
+        orders
+    }
+
     /// Orders that were not submitted but were adjudicated to ensure every unit has an order.
     pub fn generated_orders(&self) -> impl Iterator<Item = &MappedMainOrder> {
         self.civil_disorder_orders.iter()
@@ -115,11 +252,11 @@ impl<'a> Submission<'a> {
         &self,
         start: &impl UnitPositions<RegionKey>,
     ) -> (HashMap<usize, IllegalOrder>, Vec<MappedMainOrder>) {
-        let mut illegal_orders = HashMap::new();
+        let mut illegal_orders = HashMap::default();
         let mut inserted_orders = vec![];
 
         let positions = start.unit_positions().into_iter().collect::<HashSet<_>>();
-        let mut ordered_units = HashSet::new();
+        let mut ordered_units = HashSet::default();
 
         // Reject any illegal orders to prevent them being considered for the rest of
         // the resolution process.
@@ -136,12 +273,13 @@ impl<'a> Submission<'a> {
             //   is possible. An impossible order, like "A Bohemia - Edinburgh", is illegal.
             // - Illegal orders are completely ignored and do not have any influence.
             else if order.is_move()
-                && !(order
-                    .move_dest()
-                    .and_then(|d| self.world_map.find_border_between(&order.region, d))
-                    .map(|b| b.is_passable_by(order.unit_type))
-                    .unwrap_or(false)
-                    || convoy::route_may_exist(self.world_map, positions.iter().cloned(), order))
+                && !(is_adjacent_move(self.world_map, order)
+                    || convoy::route_may_exist(
+                        self.world_map,
+                        positions.iter().cloned(),
+                        order,
+                        None,
+                    ))
             {
                 illegal_orders.insert(index, IllegalOrder::UnreachableDestination);
             } else if !ordered_units.insert(order) {
@@ -175,6 +313,66 @@ impl<'a> Submission<'a> {
     }
 }
 
+/// Checks if a move order's destination borders its origin on terrain the unit can cross.
+fn is_adjacent_move(map: &Map, order: &MappedMainOrder) -> bool {
+    order
+        .move_dest()
+        .and_then(|d| map.find_border_between(&order.region, d))
+        .map(|b| b.is_passable_by(order.unit_type))
+        .unwrap_or(false)
+}
+
+impl MainCommand<RegionKey> {
+    /// Check whether this command could be legal for a unit of `unit_type` in `region`,
+    /// knowing only the map.
+    ///
+    /// A move is legal if its destination borders `region` on terrain the unit can cross,
+    /// or if an army could reach a land destination by convoy were there fleets in every
+    /// sea region. Other commands are always legal here.
+    ///
+    /// This is meant for checking orders as a player enters them. Without the positions of
+    /// units it cannot detect [`IllegalOrder::NoUnit`], [`IllegalOrder::ForeignUnit`], or
+    /// [`IllegalOrder::MultipleToSameUnit`], and an army move it accepts may still be
+    /// illegal in a [`Submission`] if there are no fleets to convoy it.
+    pub fn validate(
+        &self,
+        map: &Map,
+        region: &RegionKey,
+        unit_type: UnitType,
+    ) -> Result<(), IllegalOrder> {
+        if !self.is_move() {
+            return Ok(());
+        }
+
+        let order = Order::new(Nation::from(""), unit_type, region.clone(), self.clone());
+        if is_adjacent_move(map, &order) {
+            return Ok(());
+        }
+
+        let can_occupy_dest = order
+            .move_dest()
+            .and_then(|dest| map.find_region(&dest.to_string()))
+            .is_some_and(|dest| unit_type.can_occupy(dest.terrain()));
+        if !can_occupy_dest {
+            return Err(IllegalOrder::UnreachableDestination);
+        }
+
+        let seas = map
+            .regions()
+            .filter(|r| r.terrain() == Terrain::Sea)
+            .map(RegionKey::from)
+            .collect::<Vec<_>>();
+        let fleet = Unit::new(Cow::Owned(Nation::from("")), UnitType::Fleet);
+        let fleets = seas.iter().map(|sea| UnitPosition::new(fleet.clone(), sea));
+
+        if convoy::route_may_exist(map, fleets, &order, None) {
+            Ok(())
+        } else {
+            Err(IllegalOrder::UnreachableDestination)
+        }
+    }
+}
+
 /// Unit positions at the start of the turn.
 impl UnitPositions<RegionKey> for Submission<'_> {
     fn unit_positions(&self) -> Vec<UnitPosition<'_>> {
@@ -213,6 +411,18 @@ pub struct Context<'a, A> {
     pub world_map: &'a Map,
 
     pub(in crate::judge) illegal_orders: HashMap<&'a MappedMainOrder, IllegalOrder>,
+
+    /// Supply center ownership at the start of the turn, if known.
+    pub(in crate::judge) ownerships: Option<&'a HashMap<ProvinceKey, Nation>>,
+
+    /// Generated orders which participate in resolution but are not reported in the outcome.
+    pub(in crate::judge) hidden_orders: HashSet<&'a MappedMainOrder>,
+
+    /// Hold orders generated for units that had no legal order.
+    pub(in crate::judge) generated_orders: HashSet<&'a MappedMainOrder>,
+
+    /// Convoy routes found so far, so that repeated path checks don't redo the search.
+    pub(in crate::judge) route_cache: convoy::RouteCache,
 }
 
 impl<'a, A: Adjudicate> Context<'a, A> {
@@ -226,7 +436,11 @@ impl<'a, A: Adjudicate> Context<'a, A> {
             world_map,
             rules,
             orders: orders.into_iter().collect(),
-            illegal_orders: HashMap::new(),
+            illegal_orders: HashMap::default(),
+            ownerships: None,
+            hidden_orders: HashSet::default(),
+            generated_orders: HashSet::default(),
+            route_cache: convoy::RouteCache::default(),
         }
     }
 
@@ -244,11 +458,7 @@ impl<'a, A: Adjudicate> Context<'a, A> {
     /// tracking whether orders are successful. The two are interdependent, calling back and forth
     /// as they work towards a solution.
     pub fn resolve(self) -> Outcome<'a, A> {
-        let mut rs = ResolverState::new();
-
-        for (order, reason) in &self.illegal_orders {
-            rs.illegal_orders.insert(order, *reason);
-        }
+        let mut rs = self.initial_resolver_state();
 
         for order in self.orders() {
             rs.resolve(&self, order);
@@ -260,6 +470,205 @@ impl<'a, A: Adjudicate> Context<'a, A> {
     pub fn find_order_to_province(&self, p: &ProvinceKey) -> Option<&'a MappedMainOrder> {
         self.orders().find(|o| &o.region == p)
     }
+
+    /// Find the order for the unit in exactly `region`. Unlike
+    /// [`Context::find_order_to_province`], this distinguishes between the coasts of a
+    /// province, so a fleet on `spa(nc)` is not found by looking up `spa(sc)`.
+    pub fn find_order_to_region(&self, region: &RegionKey) -> Option<&'a MappedMainOrder> {
+        self.orders().find(|o| &o.region == region)
+    }
+
+    /// Partition the orders into groups which cannot affect one another's outcomes, in the
+    /// order they were submitted.
+    ///
+    /// Two orders are in the same group if they mention a common province, whether as the
+    /// unit's location, a move destination, or the target of a support or convoy. Every way
+    /// one order can affect another depends on such a shared province, so each group can be
+    /// resolved without considering the others.
+    pub fn clusters(&self) -> Vec<Vec<&'a MappedMainOrder>> {
+        let mut parents = (0..self.orders.len()).collect::<Vec<_>>();
+
+        fn root(parents: &mut [usize], mut idx: usize) -> usize {
+            while parents[idx] != idx {
+                parents[idx] = parents[parents[idx]];
+                idx = parents[idx];
+            }
+            idx
+        }
+
+        let mut first_mention = HashMap::default();
+        for (idx, order) in self.orders.iter().enumerate() {
+            for province in mentioned_provinces(order) {
+                let other = *first_mention.entry(province).or_insert(idx);
+                let (a, b) = (root(&mut parents, idx), root(&mut parents, other));
+                parents[a.max(b)] = a.min(b);
+            }
+        }
+
+        let mut clusters = Vec::<Vec<_>>::new();
+        let mut cluster_of_root = HashMap::default();
+        for (idx, order) in self.orders.iter().enumerate() {
+            let cluster = *cluster_of_root
+                .entry(root(&mut parents, idx))
+                .or_insert_with(|| {
+                    clusters.push(vec![]);
+                    clusters.len() - 1
+                });
+            clusters[cluster].push(*order);
+        }
+
+        clusters
+    }
+
+    /// Resolve the context one [cluster](Context::clusters) at a time.
+    ///
+    /// Under [`Rulebook`](super::Rulebook), this produces the same outcome as
+    /// [`Context::resolve`]. Each cluster is resolved with a context holding only that
+    /// cluster's orders, without the other orders or the ownerships, so a custom
+    /// [`Adjudicate`] which looks beyond the orders a cluster mentions may decide differently.
+    pub fn resolve_partitioned(self) -> Outcome<'a, A> {
+        let mut rs = self.initial_resolver_state();
+        for cluster in self.clusters() {
+            rs.absorb(resolve_cluster(self.world_map, &self.rules, cluster));
+        }
+
+        Outcome::new(self, rs)
+    }
+
+    /// Resolve the [clusters](Context::clusters) of the context on a pool of threads, one per
+    /// [available core](std::thread::available_parallelism).
+    ///
+    /// Spawning threads costs more than resolving a typical turn, so contexts with fewer than
+    /// [`MIN_PARALLEL_ORDERS`] orders, which includes every turn of a standard game, are
+    /// [partitioned](Context::resolve_partitioned) on the current thread instead. Like
+    /// partitioned resolution, this only produces the same outcome as [`Context::resolve`]
+    /// for an [`Adjudicate`] that doesn't look beyond the orders a cluster mentions, such as
+    /// [`Rulebook`](super::Rulebook).
+    #[cfg(feature = "parallel")]
+    pub fn resolve_parallel(self) -> Outcome<'a, A>
+    where
+        A: Sync,
+    {
+        let workers = std::thread::available_parallelism().map_or(1, usize::from);
+        if workers < 2 || self.orders.len() < MIN_PARALLEL_ORDERS {
+            self.resolve_partitioned()
+        } else {
+            self.resolve_on_workers(workers)
+        }
+    }
+
+    /// Resolve the clusters of the context on up to `workers` threads, giving each thread a
+    /// similar number of orders.
+    #[cfg(feature = "parallel")]
+    fn resolve_on_workers(self, workers: usize) -> Outcome<'a, A>
+    where
+        A: Sync,
+    {
+        let mut clusters = self.clusters();
+        clusters.sort_by_key(|cluster| core::cmp::Reverse(cluster.len()));
+
+        // Deal out the largest clusters first, each to the least-loaded worker.
+        let mut batches = vec![(0, vec![]); workers.min(clusters.len())];
+        for cluster in clusters {
+            let batch = batches
+                .iter_mut()
+                .min_by_key(|(size, _)| *size)
+                .expect("There is a batch for every cluster");
+            batch.0 += cluster.len();
+            batch.1.push(cluster);
+        }
+
+        let mut rs = self.initial_resolver_state();
+        let (world_map, rules) = (self.world_map, &self.rules);
+        let states = std::thread::scope(|scope| {
+            let handles = batches
+                .into_iter()
+                .map(|(_, batch)| {
+                    scope.spawn(move || {
+                        batch
+                            .into_iter()
+                            .map(|cluster| resolve_cluster(world_map, rules, cluster))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("Resolving a cluster should not panic"))
+                .collect::<Vec<_>>()
+        });
+
+        for state in states {
+            rs.absorb(state);
+        }
+
+        Outcome::new(self, rs)
+    }
+
+    fn initial_resolver_state(&self) -> ResolverState<'a> {
+        let mut rs = ResolverState::new();
+        for (order, reason) in &self.illegal_orders {
+            rs.illegal_orders.insert(order, *reason);
+        }
+        rs
+    }
+}
+
+/// The fewest orders for which [`Context::resolve_parallel`] uses more than one thread. This
+/// is well above the 34 units of a standard game, which resolve faster on one thread.
+#[cfg(feature = "parallel")]
+pub const MIN_PARALLEL_ORDERS: usize = 256;
+
+/// The durable results of resolving a cluster, which can be sent between threads.
+struct ClusterResolution<'a> {
+    state: HashMap<&'a MappedMainOrder, ResolutionState>,
+    paradoxical_orders: HashSet<&'a MappedMainOrder>,
+    #[cfg(feature = "dependency-graph")]
+    deps: BTreeSet<(MappedMainOrder, MappedMainOrder)>,
+    #[cfg(feature = "resolution-stats")]
+    stats: ResolutionStats<'a>,
+}
+
+/// Resolve a cluster of orders in isolation from the rest of the turn's orders.
+fn resolve_cluster<'a>(
+    world_map: &'a Map,
+    rules: &impl Adjudicate,
+    cluster: Vec<&'a MappedMainOrder>,
+) -> ClusterResolution<'a> {
+    let context = Context::new(world_map, rules, cluster);
+    let mut rs = ResolverState::new();
+    for order in context.orders() {
+        rs.resolve(&context, order);
+    }
+
+    ClusterResolution {
+        #[cfg(feature = "dependency-graph")]
+        deps: rs.dependencies(),
+        #[cfg(feature = "resolution-stats")]
+        stats: rs.stats,
+        state: rs.state,
+        paradoxical_orders: rs.paradoxical_orders,
+    }
+}
+
+/// The provinces an order mentions, starting with the province of the ordered unit.
+fn mentioned_provinces(order: &MappedMainOrder) -> Vec<&ProvinceKey> {
+    let mut provinces = vec![order.region.province()];
+    match &order.command {
+        MainCommand::Hold => {}
+        MainCommand::Move(cmd) => provinces.push(cmd.dest().province()),
+        MainCommand::Support(SupportedOrder::Hold(_, target)) => provinces.push(target.province()),
+        MainCommand::Support(SupportedOrder::Move(_, from, to)) => {
+            provinces.push(from.province());
+            provinces.push(to.province());
+        }
+        MainCommand::Convoy(cm) => {
+            provinces.push(cm.from().province());
+            provinces.push(cm.to().province());
+        }
+    }
+    provinces
 }
 
 #[allow(clippy::implicit_hasher)]
@@ -295,8 +704,8 @@ impl ResolutionState {
     }
 }
 
-impl std::fmt::Debug for ResolutionState {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Debug for ResolutionState {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(
             f,
             "{}({:?})",
@@ -306,6 +715,40 @@ impl std::fmt::Debug for ResolutionState {
     }
 }
 
+/// A change made to a `ResolverState` while a checkpoint was open, recorded so that
+/// the change can be reverted if the guess that caused it is abandoned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Undo<'a> {
+    /// The state of an order was changed; this holds the prior state.
+    State(&'a MappedMainOrder, Option<ResolutionState>),
+    /// An order was newly marked as paradoxical.
+    Paradox(&'a MappedMainOrder),
+    /// An order was pushed onto the dependency chain.
+    ChainPush,
+    /// An order was popped from the dependency chain.
+    ChainPop(&'a MappedMainOrder),
+}
+
+/// A point in resolution to which a `ResolverState` can be rolled back.
+#[must_use]
+struct Checkpoint(usize);
+
+/// Counts of the work done to resolve a turn, produced by
+/// [`Outcome::resolution_stats`](super::Outcome::resolution_stats).
+///
+/// Most orders are resolved once with a single guess. Orders that are resolved many times, or
+/// turns with many guesses, indicate positions where cycles and paradoxes make adjudication
+/// expensive.
+#[cfg(feature = "resolution-stats")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResolutionStats<'a> {
+    /// The number of times each order's outcome was worked out, rather than looked up from an
+    /// earlier resolution. Orders that were never worked out are omitted.
+    pub resolutions: HashMap<&'a MappedMainOrder, usize>,
+    /// The number of guesses made about the outcomes of orders, across all orders.
+    pub guesses: usize,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ResolverState<'a> {
     state: HashMap<&'a MappedMainOrder, ResolutionState>,
@@ -320,45 +763,114 @@ pub struct ResolverState<'a> {
     /// A set containing directed edges in a graph of order dependencies.
     #[cfg(feature = "dependency-graph")]
     deps: Rc<RefCell<BTreeSet<(MappedMainOrder, MappedMainOrder)>>>,
+    /// Counts of the work done during resolution. These are not rolled back with guesses.
+    #[cfg(feature = "resolution-stats")]
+    stats: ResolutionStats<'a>,
     /// The conservative dependency chain used to trigger cycle detection. This contains
     /// guesses that have been visited twice, indicating that a cycle has been found.
     dependency_chain: Vec<&'a MappedMainOrder>,
 
+    /// Changes made since the outermost open checkpoint, in the order they were made.
+    undo_log: Vec<Undo<'a>>,
+    /// The number of checkpoints which have been neither rolled back nor released.
+    open_checkpoints: usize,
+
     pub(in crate::judge) illegal_orders: HashMap<&'a MappedMainOrder, IllegalOrder>,
 }
 
 impl<'a> ResolverState<'a> {
     /// Create a new resolver for a given rulebook.
     pub fn new() -> Self {
-        #[cfg(feature = "dependency-graph")]
-        {
-            ResolverState {
-                state: HashMap::new(),
-                deps: Rc::new(RefCell::new(BTreeSet::default())),
-                greedy_chain: vec![],
-                dependency_chain: vec![],
-                paradoxical_orders: HashSet::new(),
-                illegal_orders: HashMap::new(),
-            }
+        ResolverState {
+            state: HashMap::default(),
+            #[cfg(feature = "dependency-graph")]
+            deps: Rc::new(RefCell::new(BTreeSet::default())),
+            #[cfg(feature = "dependency-graph")]
+            greedy_chain: vec![],
+            #[cfg(feature = "resolution-stats")]
+            stats: ResolutionStats::default(),
+            dependency_chain: vec![],
+            paradoxical_orders: HashSet::default(),
+            undo_log: vec![],
+            open_checkpoints: 0,
+            illegal_orders: HashMap::default(),
         }
+    }
 
-        #[cfg(not(feature = "dependency-graph"))]
-        {
-            ResolverState {
-                state: HashMap::new(),
-                dependency_chain: vec![],
-                paradoxical_orders: HashSet::new(),
-                illegal_orders: HashMap::new(),
-            }
+    fn clear_state(&mut self, order: &'a MappedMainOrder) {
+        let prior = self.state.remove(order);
+        self.record(Undo::State(order, prior));
+    }
+
+    fn set_state(&mut self, order: &'a MappedMainOrder, resolution: ResolutionState) {
+        let prior = self.state.insert(order, resolution);
+        self.record(Undo::State(order, prior));
+    }
+
+    fn mark_paradoxical(&mut self, order: &'a MappedMainOrder) {
+        if self.paradoxical_orders.insert(order) {
+            self.record(Undo::Paradox(order));
         }
     }
 
-    fn clear_state(&mut self, order: &MappedMainOrder) {
-        self.state.remove(order);
+    fn push_dependency(&mut self, order: &'a MappedMainOrder) {
+        self.dependency_chain.push(order);
+        self.record(Undo::ChainPush);
     }
 
-    fn set_state(&mut self, order: &'a MappedMainOrder, resolution: ResolutionState) {
-        self.state.insert(order, resolution);
+    fn pop_dependency(&mut self) {
+        if let Some(order) = self.dependency_chain.pop() {
+            self.record(Undo::ChainPop(order));
+        }
+    }
+
+    /// Record a change so it can be undone. Changes made outside any checkpoint can never be
+    /// rolled back, so they aren't recorded.
+    fn record(&mut self, undo: Undo<'a>) {
+        if self.open_checkpoints > 0 {
+            self.undo_log.push(undo);
+        }
+    }
+
+    /// Start recording changes so that they can be rolled back.
+    fn checkpoint(&mut self) -> Checkpoint {
+        self.open_checkpoints += 1;
+        Checkpoint(self.undo_log.len())
+    }
+
+    /// Keep the changes made since `checkpoint`. They can still be rolled back by an
+    /// enclosing checkpoint.
+    fn release(&mut self, checkpoint: Checkpoint) {
+        debug_assert!(checkpoint.0 <= self.undo_log.len());
+        self.open_checkpoints -= 1;
+        if self.open_checkpoints == 0 {
+            self.undo_log.clear();
+        }
+    }
+
+    /// Revert all changes made since `checkpoint`, newest first.
+    fn rollback(&mut self, checkpoint: Checkpoint) {
+        while self.undo_log.len() > checkpoint.0 {
+            match self.undo_log.pop().expect("Undo log should not be empty") {
+                Undo::State(order, Some(prior)) => {
+                    self.state.insert(order, prior);
+                }
+                Undo::State(order, None) => {
+                    self.state.remove(order);
+                }
+                Undo::Paradox(order) => {
+                    self.paradoxical_orders.remove(order);
+                }
+                Undo::ChainPush => {
+                    self.dependency_chain.pop();
+                }
+                Undo::ChainPop(order) => {
+                    self.dependency_chain.push(order);
+                }
+            }
+        }
+
+        self.release(checkpoint);
     }
 
     fn knows_outcome_of(&self, order: &MappedMainOrder) -> bool {
@@ -372,41 +884,37 @@ impl<'a> ResolverState<'a> {
         self.paradoxical_orders.contains(order)
     }
 
-    /// Create a clone of the resolver state, add a guess at the success or failure
-    /// of the given order, then adjudicate the order with the amended resolver.
+    /// Add a guess at the success or failure of the given order, then adjudicate the order
+    /// with the amended state.
     ///
-    /// This returns the entire guesser because in some cases the calling resolver needs
-    /// the dependency chain and the entire state generated during the post-guess adjudication.
-    fn with_guess<'b>(
-        &self,
-        context: &'b Context<'a, impl Adjudicate>,
+    /// As in the original C implementation, the guess and everything learned while adjudicating
+    /// it are applied directly to `self`. Callers should take a checkpoint first so that they
+    /// can unwind guesses that don't work out.
+    fn with_guess(
+        &mut self,
+        context: &Context<'a, impl Adjudicate>,
         order: &'a MappedMainOrder,
         guess: OrderState,
-    ) -> (ResolverState<'a>, OrderState) {
-        let mut guesser = self.clone();
-
+    ) -> OrderState {
         #[cfg(feature = "dependency-graph")]
         {
-            guesser.greedy_chain.push(order);
+            self.greedy_chain.push(order);
         }
 
-        guesser.set_state(order, ResolutionState::guessing(guess));
-        let result = context.rules.adjudicate(context, &mut guesser, order);
-        (guesser, result)
-    }
+        #[cfg(feature = "resolution-stats")]
+        {
+            self.stats.guesses += 1;
+        }
 
-    /// Take durable state from another `ResolverState`, leaving behind intermediate calculations.
-    ///
-    /// In the original C implementation, this wasn't necessary because hypotheticals directly modified
-    /// the speculating resolver state. That requires the C implementation to unwind guesses that don't
-    /// work out, but allows it to do nothing to leverage successful resolutions.
-    ///
-    /// The Rust implementation does the opposite, so it needs to "snap to" a resolution state that it
-    /// wants to keep.
-    fn snap_to(&mut self, other: Self) {
-        self.state = other.state;
-        self.paradoxical_orders = other.paradoxical_orders;
-        self.dependency_chain = other.dependency_chain;
+        self.set_state(order, ResolutionState::guessing(guess));
+        let result = context.rules.adjudicate(context, self, order);
+
+        #[cfg(feature = "dependency-graph")]
+        {
+            self.greedy_chain.pop();
+        }
+
+        result
     }
 
     /// When a dependency cycle is detected, attempt to resolve all orders in the cycle.
@@ -420,13 +928,13 @@ impl<'a> ResolverState<'a> {
             }
         } else {
             for o in cycle {
-                self.dependency_chain.pop();
+                self.pop_dependency();
                 if self.knows_outcome_of(o) {
                     continue;
                 }
 
                 if let MainCommand::Convoy(_) = o.command {
-                    self.paradoxical_orders.insert(o);
+                    self.mark_paradoxical(o);
                     self.set_state(o, ResolutionState::known(OrderState::Fails));
                 } else {
                     self.clear_state(o);
@@ -454,43 +962,55 @@ impl<'a> ResolverState<'a> {
             }
         }
 
-        if let Some(state) = self.state.get(order) {
+        if let Some(state) = self.state.get(order).copied() {
             if state.is_guess() {
                 // In recursive cases, we accumulate dependencies
                 if !self.dependency_chain.contains(&order) {
-                    self.dependency_chain.push(order)
+                    self.push_dependency(order)
                 }
             }
 
             return state.order_state;
         }
 
+        #[cfg(feature = "resolution-stats")]
+        {
+            *self.stats.resolutions.entry(order).or_default() += 1;
+        }
+
         // checkpoint the resolver and tell it to assume the order fails.
         // get the order state based on that assumption.
-        let (first_resolver, first_result) = self.with_guess(context, order, Fails);
+        let chain_len = self.dependency_chain.len();
+        let checkpoint = self.checkpoint();
+        let first_result = self.with_guess(context, order, Fails);
 
         // If we found no new dependencies then this is a valid resolution!
-        // We now snap to the resolver state from the assumption so that we can
+        // We keep the state learned under the assumption so that we can
         // reuse it in future calculations.
-        if first_resolver.dependency_chain.len() == self.dependency_chain.len() {
-            self.snap_to(first_resolver);
+        if self.dependency_chain.len() == chain_len {
+            self.release(checkpoint);
             self.set_state(order, ResolutionState::known(first_result));
             first_result
         } else {
-            let next_dep = first_resolver.dependency_chain[self.dependency_chain.len()];
+            let next_dep = self.dependency_chain[chain_len];
 
             // if we depend on some new guess but we haven't hit a cycle,
-            // then we cautiously proceed. We update state to match what we've learned
+            // then we cautiously proceed. We keep what we've learned
             // from the hypothetical and proceed with our guesses.
             if next_dep != order {
-                self.snap_to(first_resolver);
+                self.release(checkpoint);
                 self.set_state(order, ResolutionState::guessing(first_result));
-                self.dependency_chain.push(order);
+                self.push_dependency(order);
                 first_result
             }
             // if the next dependency is the one we're already depending on, we're stuck.
             else {
-                let (_second_resolver, second_result) = self.with_guess(context, order, Succeeds);
+                let tail = self.dependency_chain[chain_len..].to_vec();
+                self.rollback(checkpoint);
+
+                let checkpoint = self.checkpoint();
+                let second_result = self.with_guess(context, order, Succeeds);
+                self.rollback(checkpoint);
 
                 // If there's a paradox but the outcome doesn't depend on this order,
                 // then all we've learned is the state of this one order.
@@ -498,21 +1018,43 @@ impl<'a> ResolverState<'a> {
                     self.set_state(order, ResolutionState::known(first_result));
                     first_result
                 } else {
-                    let tail_start = self.dependency_chain.len();
-                    let tail = &first_resolver.dependency_chain[tail_start..];
-
-                    self.resolve_dependency_cycle(tail);
+                    self.resolve_dependency_cycle(&tail);
                     self.resolve(context, order)
                 }
             }
         }
     }
 
+    /// Take the resolutions from a state that resolved an independent set of orders.
+    fn absorb(&mut self, other: ClusterResolution<'a>) {
+        self.state.extend(other.state);
+        self.paradoxical_orders.extend(other.paradoxical_orders);
+
+        #[cfg(feature = "dependency-graph")]
+        {
+            self.deps.borrow_mut().extend(other.deps);
+        }
+
+        #[cfg(feature = "resolution-stats")]
+        {
+            self.stats.guesses += other.stats.guesses;
+            for (order, count) in other.stats.resolutions {
+                *self.stats.resolutions.entry(order).or_default() += count;
+            }
+        }
+    }
+
     /// Get the set of inter-order dependencies encountered while resolving this
     #[cfg(feature = "dependency-graph")]
     pub(crate) fn dependencies(&self) -> BTreeSet<(MappedMainOrder, MappedMainOrder)> {
         self.deps.borrow().clone()
     }
+
+    /// Get the counts of the work done while resolving this
+    #[cfg(feature = "resolution-stats")]
+    pub(crate) fn stats(&self) -> &ResolutionStats<'a> {
+        &self.stats
+    }
 }
 
 impl Default for ResolverState<'_> {
@@ -524,7 +1066,7 @@ impl Default for ResolverState<'_> {
 #[allow(clippy::implicit_hasher)]
 impl<'a> From<ResolverState<'a>> for HashMap<MappedMainOrder, OrderState> {
     fn from(state: ResolverState<'a>) -> Self {
-        let mut out_map = HashMap::with_capacity(state.state.len());
+        let mut out_map = HashMap::with_capacity_and_hasher(state.state.len(), Default::default());
 
         for (order, order_state) in state.state {
             out_map.insert(order.clone(), order_state.order_state);
@@ -533,3 +1075,82 @@ impl<'a> From<ResolverState<'a>> for HashMap<MappedMainOrder, OrderState> {
         out_map
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Context;
+    use crate::geo::{standard_map, Coast, ProvinceKey, RegionKey};
+    use crate::judge::{MappedMainOrder, Rulebook};
+
+    #[test]
+    fn find_order_to_region() {
+        let north = RegionKey::new("spa", Coast::North);
+        let south = RegionKey::new("spa", Coast::South);
+
+        for (order, occupied, empty) in [
+            ("FRA: F spa(nc) Hold", &north, &south),
+            ("FRA: F spa(sc) Hold", &south, &north),
+        ] {
+            let orders = vec![order.parse::<MappedMainOrder>().unwrap()];
+            let context = Context::new(standard_map(), Rulebook::default(), &orders);
+            assert_eq!(context.find_order_to_region(occupied), Some(&orders[0]));
+            assert_eq!(context.find_order_to_region(empty), None);
+            assert_eq!(
+                context.find_order_to_province(&ProvinceKey::new("spa")),
+                Some(&orders[0])
+            );
+        }
+    }
+
+    #[test]
+    fn clusters() {
+        let orders = [
+            "ENG: A lon -> bel",
+            "FRA: A par -> bur",
+            "ENG: F nth convoys lon -> bel",
+            "GER: A mun -> bur",
+            "TUR: A con -> bul",
+            "GER: A ruh Supports A mun -> bur",
+        ]
+        .into_iter()
+        .map(|o| o.parse::<MappedMainOrder>().unwrap())
+        .collect::<Vec<_>>();
+
+        let context = Context::new(standard_map(), Rulebook::default(), &orders);
+        assert_eq!(
+            context.clusters(),
+            vec![
+                vec![&orders[0], &orders[2]],
+                vec![&orders[1], &orders[3], &orders[5]],
+                vec![&orders[4]],
+            ]
+        );
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn resolve_on_workers() {
+        let orders = [
+            "ENG: A lon -> bel",
+            "FRA: A par -> bur",
+            "ENG: F nth convoys lon -> bel",
+            "GER: A mun -> bur",
+            "TUR: A con -> bul",
+            "GER: A ruh Supports A mun -> bur",
+            "RUS: A war -> gal",
+            "AUS: A vie -> gal",
+        ]
+        .into_iter()
+        .map(|o| o.parse::<MappedMainOrder>().unwrap())
+        .collect::<Vec<_>>();
+
+        let expected = Context::new(standard_map(), Rulebook::default(), &orders).resolve();
+        for workers in [1, 2, 3, 8] {
+            let outcome = Context::new(standard_map(), Rulebook::default(), &orders)
+                .resolve_on_workers(workers);
+            for order in &orders {
+                assert_eq!(outcome.get(order), expected.get(order), "{}", order);
+            }
+        }
+    }
+}
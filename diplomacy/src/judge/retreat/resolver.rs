@@ -1,8 +1,13 @@
 use super::{DestStatus, Start};
-use crate::judge::MappedRetreatOrder;
+use crate::collections::{HashMap, HashSet};
+use crate::judge::build::WorldState;
+use crate::judge::{MappedRetreatOrder, PhaseOutcome};
 use crate::order::{Command, RetreatCommand};
-use crate::{geo::ProvinceKey, geo::RegionKey, Unit, UnitPosition, UnitPositions};
-use std::collections::HashMap;
+use crate::{
+    geo::ProvinceKey, geo::RegionKey, Nation, Unit, UnitPosition, UnitPositions, UnitType,
+};
+use alloc::vec::Vec;
+use core::fmt;
 
 /// The immutable parts of retreat phase adjudication.
 pub struct Context<'a> {
@@ -19,9 +24,10 @@ impl<'a> Context<'a> {
     }
 
     /// Adjudicate a retreat phase and determine which units move or are disbanded.
-    pub fn resolve(&self) -> Outcome {
-        let mut outcomes = HashMap::new();
-        let mut destinations = HashMap::new();
+    pub fn resolve(&self) -> Outcome<'_> {
+        let mut outcomes = HashMap::default();
+        let mut destinations = HashMap::default();
+        let mut ordered_units = HashSet::default();
 
         for order in &self.orders {
             let dests = if let Some(dests) = self
@@ -35,8 +41,16 @@ impl<'a> Context<'a> {
                 continue;
             };
 
+            // Only the first order to each unit is used, mirroring the main phase.
+            if !ordered_units.insert(order.unit_position()) {
+                outcomes
+                    .entry(order)
+                    .or_insert(OrderOutcome::MultipleToSameUnit);
+                continue;
+            }
+
             match &order.command {
-                RetreatCommand::Hold => {
+                RetreatCommand::Hold | RetreatCommand::Disband => {
                     outcomes.insert(order, OrderOutcome::DisbandsAsOrdered);
                 }
                 RetreatCommand::Move(dest) => match dests.get(dest) {
@@ -87,10 +101,7 @@ impl<'a> Outcome<'a> {
         }
     }
 
-    pub fn get(
-        &'a self,
-        order: &MappedRetreatOrder,
-    ) -> Option<&'a OrderOutcome<&'a MappedRetreatOrder>> {
+    pub fn get(&self, order: &MappedRetreatOrder) -> Option<&OrderOutcome<&'a MappedRetreatOrder>> {
         self.by_order.get(order)
     }
 
@@ -100,22 +111,102 @@ impl<'a> Outcome<'a> {
     ) -> impl Iterator<Item = (&MappedRetreatOrder, &OrderOutcome<&'a MappedRetreatOrder>)> {
         self.by_order.iter().map(|(k, v)| (*k, v))
     }
+
+    /// The position of every unit at the end of the retreat phase, sorted by region.
+    ///
+    /// Units that retreated are in their destinations; dislodged units that disbanded or
+    /// failed to retreat are omitted. This is the board the next phase starts from.
+    pub fn resulting_positions(&self) -> Vec<UnitPosition<'static, RegionKey>> {
+        let mut positions = self
+            .unit_positions
+            .values()
+            .map(UnitPosition::to_owned)
+            .collect::<Vec<_>>();
+        positions.sort_by(|a, b| a.region.cmp(&b.region));
+        positions
+    }
+
+    /// Copy the outcome into a [`Report`], which does not borrow the retreat phase's start
+    /// or orders.
+    pub fn to_report(&self) -> Report {
+        Report {
+            orders: self
+                .order_outcomes()
+                .map(|(ord, outcome)| (ord.clone(), outcome.map_order(Clone::clone)))
+                .collect(),
+            resulting_positions: self.resulting_positions(),
+        }
+    }
+}
+
+/// An owned copy of a retreat phase [`Outcome`], produced by [`Outcome::to_report`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    /// The outcome of each retreat order.
+    pub orders: HashMap<MappedRetreatOrder, OrderOutcome<MappedRetreatOrder>>,
+    /// The position of every unit at the end of the retreat phase, sorted by region.
+    pub resulting_positions: Vec<UnitPosition<'static, RegionKey>>,
+}
+
+impl<'a> PhaseOutcome for Outcome<'a> {
+    type Order = MappedRetreatOrder;
+    type OrderOutcome = OrderOutcome<&'a MappedRetreatOrder>;
+
+    fn get(&self, order: &MappedRetreatOrder) -> Option<&Self::OrderOutcome> {
+        Outcome::get(self, order)
+    }
+
+    fn order_outcomes(&self) -> impl Iterator<Item = (&MappedRetreatOrder, &Self::OrderOutcome)> {
+        Outcome::order_outcomes(self)
+    }
 }
 
 impl UnitPositions<RegionKey> for Outcome<'_> {
-    fn unit_positions(&self) -> Vec<UnitPosition> {
+    fn unit_positions(&self) -> Vec<UnitPosition<'_>> {
         self.unit_positions.unit_positions()
     }
 
-    fn find_province_occupier(&self, province: &ProvinceKey) -> Option<UnitPosition> {
+    fn find_province_occupier(&self, province: &ProvinceKey) -> Option<UnitPosition<'_>> {
         self.unit_positions.find_province_occupier(province)
     }
 
-    fn find_region_occupier(&self, region: &RegionKey) -> Option<Unit> {
+    fn find_region_occupier(&self, region: &RegionKey) -> Option<Unit<'_>> {
         self.unit_positions.find_region_occupier(region)
     }
 }
 
+/// The units left at the end of a retreat phase, which are the world state of the build phase
+/// that follows it.
+impl WorldState for Outcome<'_> {
+    fn nations(&self) -> HashSet<&Nation> {
+        self.unit_positions
+            .values()
+            .map(|pos| pos.nation())
+            .collect()
+    }
+
+    fn occupier(&self, province: &ProvinceKey) -> Option<&Nation> {
+        self.unit_positions.get(province).map(|pos| pos.nation())
+    }
+
+    fn unit_count(&self, nation: &Nation) -> u8 {
+        self.unit_positions
+            .values()
+            .filter(|pos| pos.nation() == nation)
+            .count()
+            .try_into()
+            .unwrap()
+    }
+
+    fn units(&self, nation: &Nation) -> HashSet<(UnitType, RegionKey)> {
+        self.unit_positions
+            .values()
+            .filter(|pos| pos.nation() == nation)
+            .map(|pos| (pos.unit.unit_type(), pos.region.clone()))
+            .collect()
+    }
+}
+
 /// The outcome of a specific retreat phase order.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -129,12 +220,39 @@ pub enum OrderOutcome<O> {
     ///
     /// The region may be vacant, or may contain a unit that was not dislodged.
     InvalidRecipient,
+    /// The nation issued multiple orders to the same unit, and this order was discarded
+    /// in favor of the first one.
+    MultipleToSameUnit,
     /// The unit successfully retreats to a new region
     Moves,
     /// The unit was ordered to disband and did so.
     DisbandsAsOrdered,
 }
 
+/// A concise description for logs, such as `Prevented by GER: A mun -> sil`.
+impl<O: fmt::Display> fmt::Display for OrderOutcome<O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use OrderOutcome::*;
+        match self {
+            Prevented(by) => write!(f, "Prevented by {}", by),
+            InvalidDestination(status) => {
+                let reason = match status {
+                    DestStatus::Available => "available",
+                    DestStatus::Unreachable => "unreachable",
+                    DestStatus::BlockedByDislodger => "blocked by dislodger",
+                    DestStatus::Occupied => "occupied",
+                    DestStatus::Contested => "contested",
+                };
+                write!(f, "Fails: destination {}", reason)
+            }
+            InvalidRecipient => write!(f, "Fails: no retreating unit"),
+            MultipleToSameUnit => write!(f, "Fails: multiple orders to the same unit"),
+            Moves => write!(f, "Succeeds"),
+            DisbandsAsOrdered => write!(f, "Disbands"),
+        }
+    }
+}
+
 impl<O> OrderOutcome<O> {
     /// Apply a function to any orders referenced by `self`, returning a new outcome.
     pub fn map_order<U>(self, map_fn: impl Fn(O) -> U) -> OrderOutcome<U> {
@@ -143,6 +261,7 @@ impl<O> OrderOutcome<O> {
             Prevented(o) => Prevented(map_fn(o)),
             InvalidDestination(status) => InvalidDestination(status),
             InvalidRecipient => InvalidRecipient,
+            MultipleToSameUnit => MultipleToSameUnit,
             Moves => Moves,
             DisbandsAsOrdered => DisbandsAsOrdered,
         }
@@ -151,7 +270,7 @@ impl<O> OrderOutcome<O> {
     /// Check if the ordered unit disbanded at the conclusion of the retreat phase.
     pub fn did_disband(&self) -> bool {
         match self {
-            Self::Moves | Self::InvalidRecipient => false,
+            Self::Moves | Self::InvalidRecipient | Self::MultipleToSameUnit => false,
             Self::Prevented(_) | Self::InvalidDestination(_) | Self::DisbandsAsOrdered => true,
         }
     }
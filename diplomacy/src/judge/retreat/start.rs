@@ -1,11 +1,12 @@
+use crate::collections::HashMap;
 use crate::geo::{Border, ProvinceKey, RegionKey};
 use crate::judge::{
     calc::dislodger_of, calc::prevent_results, convoy, Adjudicate, Context, MappedMainOrder,
     OrderState, Outcome, Prevent, ResolverState,
 };
 use crate::{order::Command, Unit, UnitPosition, UnitPositions};
-use std::collections::{BTreeMap, BTreeSet, HashMap};
-use std::iter;
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::iter;
 
 /// Data needed to adjudicate the retreat phase and to present players with useful UI for submitting
 /// retreat orders.
@@ -23,7 +24,7 @@ impl<'a> Start<'a> {
     pub fn new(outcome: &'a Outcome<'a, impl Adjudicate>) -> Self {
         let mut state = outcome.resolver.clone();
         let dislodged = {
-            let mut dislodged = HashMap::new();
+            let mut dislodged = HashMap::default();
             for order in outcome.context.orders() {
                 if let Some(dl_ord) = dislodger_of(&outcome.context, &mut state, order) {
                     dislodged.insert(order, dl_ord);
@@ -226,7 +227,7 @@ fn non_dislodged_positions<'a, A>(
     outcome: &Outcome<'a, A>,
     dislodged: &HashMap<&MappedMainOrder, &MappedMainOrder>,
 ) -> HashMap<&'a ProvinceKey, UnitPosition<'a>> {
-    let mut positions = HashMap::new();
+    let mut positions = HashMap::default();
     for (order, result) in &outcome.orders {
         if dislodged.contains_key(order) {
             continue;
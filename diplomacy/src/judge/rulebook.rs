@@ -1,7 +1,8 @@
+use super::build::{BuildPolicy, DisbandDistance};
 use super::calc::{dislodger_of, is_head_to_head, max_prevent_result, path_exists};
-use super::convoy::ConvoyOutcome;
+use super::convoy::{self, ConvoyOutcome};
 use super::resolver::{Context, ResolverState};
-use super::support::{self, SupportOutcome};
+use super::support::{self, DislodgedSupport, DisruptedConvoyCut, SupportCoast, SupportOutcome};
 use super::{Adjudicate, MappedMainOrder, OrderOutcome, OrderState};
 use crate::geo::Terrain;
 use crate::judge::strength::Strength;
@@ -9,13 +10,69 @@ use crate::order::Command;
 use crate::ShortName;
 
 /// The standard Diplomacy rules.
+///
+/// The default rulebook follows the DATC preferences. Fields on the rulebook opt into
+/// rule variants used by other editions or by popular game variants.
 #[derive(Debug, Clone, Default)]
-pub struct Rulebook;
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct Rulebook {
+    /// Where nations are allowed to build new units during the build phase.
+    pub build_policy: BuildPolicy,
+    /// Which supply centers civil disorder measures distance from when choosing units to disband.
+    pub disband_distance: DisbandDistance,
+    /// Whether dislodging a supporting unit cuts its support for an attack on the dislodger.
+    pub dislodged_support: DislodgedSupport,
+    /// Whether an army whose convoy was disrupted can still cut support.
+    pub disrupted_convoy_cut: DisruptedConvoyCut,
+    /// The most fleets a convoy route may use, or `None` for no limit.
+    ///
+    /// Finding every convoy route is expensive on maps with large connected bodies of water,
+    /// so servers accepting custom maps may want to bound it. Routes on the standard map are
+    /// short, so a limit is not needed there. Moves whose only routes are too long fail for
+    /// lack of a path; they are not treated as illegal by the [`Submission`](super::Submission).
+    pub max_convoy_length: Option<usize>,
+    /// Whether a support that omits the coast of its destination can support a move to the
+    /// only coast the moving unit could reach.
+    pub support_coast: SupportCoast,
+    /// Whether a unit can dislodge a unit of its own nation, or be helped to dislodge one by
+    /// that nation's supports.
+    pub self_dislodgement: SelfDislodgement,
+    /// Whether a nation's support for a foreign unit counts against its own unit in a
+    /// head-to-head battle.
+    pub help_dislodging_own_unit: HelpDislodgingOwnUnit,
+}
+
+/// Whether a unit can dislodge a unit of its own nation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SelfDislodgement {
+    /// A move against a unit of the same nation that stays in its province fails, and a
+    /// nation's supports do not count toward dislodging its own unit, per DATC 6.D.10 to 6.D.14.
+    #[default]
+    Prohibited,
+    /// Units of the same nation are attacked like any other unit, and all supports count.
+    Allowed,
+}
+
+/// Whether a nation's support can help a foreign unit win a head-to-head battle against one of
+/// that nation's own units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HelpDislodgingOwnUnit {
+    /// The support does not count toward the foreign unit's head-to-head strength, per
+    /// DATC 6.E.3.
+    #[default]
+    Prohibited,
+    /// The support counts like any other.
+    Allowed,
+}
 
 impl Rulebook {
     /// Apply rules to determine hold outcome.
     fn adjudicate_hold<'a>(
-        ctx: &Context<'a, Self>,
+        ctx: &Context<'a, impl Adjudicate>,
         rslv: &mut ResolverState<'a>,
         ord: &'a MappedMainOrder,
     ) -> HoldOutcome<&'a MappedMainOrder> {
@@ -26,14 +83,18 @@ impl Rulebook {
 
     /// Apply rules to determine move outcome.
     fn adjudicate_move<'a>(
-        ctx: &Context<'a, Self>,
+        ctx: &Context<'a, impl Adjudicate>,
         rslv: &mut ResolverState<'a>,
         ord: &'a MappedMainOrder,
     ) -> AttackOutcome<&'a MappedMainOrder> {
         if ord.command.move_dest() == Some(&ord.region) {
             AttackOutcome::MoveToSelf
         } else if !path_exists(ctx, rslv, ord) {
-            AttackOutcome::NoPath
+            if convoy::ordered_route_exists(ctx, ord) {
+                AttackOutcome::ConvoyDisrupted
+            } else {
+                AttackOutcome::NoPath
+            }
         } else if ord.command.is_move() {
             let mut atk_supports = support::find_for(ctx, rslv, ord);
             let mut atk_strength = 1 + atk_supports.len();
@@ -81,12 +142,17 @@ impl Rulebook {
                         // ATTACK STRENGTH of three.
                         let mut resisting_supports = support::find_for(ctx, rslv, occupier);
 
-                        let resistance = 1 + resisting_supports.len();
+                        let resistance =
+                            1 + resisting_supports.len() + ctx.rules.defense_bonus(occupier);
 
                         if is_head_to_head {
                             // Make sure the head-to-head opponent is not getting head-to-head support that would result in
                             // `ord` losing from `ord`'s own nation.
-                            resisting_supports.retain(|support| support.nation != ord.nation);
+                            if ctx.rules.policies().help_dislodging_own_unit
+                                == HelpDislodgingOwnUnit::Prohibited
+                            {
+                                resisting_supports.retain(|support| support.nation != ord.nation);
+                            }
                             (resistance, 1 + resisting_supports.len())
                         } else {
                             (resistance, 0)
@@ -94,24 +160,32 @@ impl Rulebook {
                     }
                     // failed exits resist with strength 1 (the unit trapped in the province)
                     else if rslv.resolve(ctx, occupier) == OrderState::Fails {
-                        (1, 0)
+                        (1 + ctx.rules.defense_bonus(occupier), 0)
                     // successful exits mount no resistance
                     } else {
                         (0, 0)
                     };
 
+                    let self_dislodgement_prohibited =
+                        ctx.rules.policies().self_dislodgement == SelfDislodgement::Prohibited;
+
                     // A unit can not dislodge a unit of the same player.
                     // Head-to-head, failed exit, and hold cases all collapse in friendly fire.
-                    if resistance > 0 && ord.nation == occupier.nation {
+                    if resistance > 0
+                        && self_dislodgement_prohibited
+                        && ord.nation == occupier.nation
+                    {
                         return AttackOutcome::FriendlyFire;
                     } else if resistance > 0 {
-                        let self_defend_strength = atk_strength;
+                        let self_defend_strength = atk_strength + ctx.rules.defense_bonus(ord);
 
                         // Supports to a foreign unit can not be used to dislodge an own unit.
                         // Therefore, we remove any move supports from the nation whose unit
                         // is resisting the move.
-                        atk_supports.retain(|sup| sup.nation != occupier.nation);
-                        atk_strength = 1 + atk_supports.len();
+                        if self_dislodgement_prohibited {
+                            atk_supports.retain(|sup| sup.nation != occupier.nation);
+                            atk_strength = 1 + atk_supports.len();
+                        }
 
                         // Re-check if the attack strength is sufficient to overcome prevent
                         // strength now that friendly-fire support is ignored; see 6.E.7
@@ -122,7 +196,7 @@ impl Rulebook {
                         // Only lose a head-to-head if the head-to-head opponent's attack strength
                         // is higher than our defend strength.
                         if self_defend_strength < h2h {
-                            return AttackOutcome::LostHeadToHead;
+                            return AttackOutcome::LostHeadToHead(occupier);
                         }
 
                         if atk_strength <= resistance {
@@ -139,7 +213,7 @@ impl Rulebook {
     }
 
     fn adjudicate_support<'a>(
-        ctx: &Context<'a, Self>,
+        ctx: &Context<'a, impl Adjudicate>,
         rslv: &mut ResolverState<'a>,
         ord: &'a MappedMainOrder,
     ) -> SupportOutcome<&'a MappedMainOrder> {
@@ -156,7 +230,7 @@ impl Rulebook {
     }
 
     fn adjudicate_convoy<'a>(
-        ctx: &Context<'a, Self>,
+        ctx: &Context<'a, impl Adjudicate>,
         rslv: &mut ResolverState<'a>,
         ord: &'a MappedMainOrder,
     ) -> ConvoyOutcome<&'a MappedMainOrder> {
@@ -180,8 +254,17 @@ impl Rulebook {
             return ConvoyOutcome::Dislodged(dislodger);
         }
 
+        // Test case 6.F.5: An attack that fails to dislodge the fleet doesn't disrupt the
+        // convoy, which is worth explaining to the attacker. Moves by the fleet's own nation
+        // and moves with no path can't dislodge it, so they aren't attacks.
         if rslv.order_in_paradox(ord) {
             ConvoyOutcome::Paradox
+        } else if ctx.orders().any(|o| {
+            o.nation != ord.nation
+                && o.is_move_to_province(ord.region.province())
+                && path_exists(ctx, rslv, o)
+        }) {
+            ConvoyOutcome::SurvivedAttack
         } else {
             ConvoyOutcome::NotDisrupted
         }
@@ -191,16 +274,20 @@ impl Rulebook {
 impl Adjudicate for Rulebook {
     fn adjudicate<'a>(
         &self,
-        context: &Context<'a, Self>,
+        context: &Context<'a, impl Adjudicate>,
         resolver: &mut ResolverState<'a>,
         order: &'a MappedMainOrder,
     ) -> OrderState {
         self.explain(context, resolver, order).into()
     }
 
+    fn policies(&self) -> &Rulebook {
+        self
+    }
+
     fn explain<'a>(
         &self,
-        context: &Context<'a, Self>,
+        context: &Context<'a, impl Adjudicate>,
         resolver: &mut ResolverState<'a>,
         order: &'a MappedMainOrder,
     ) -> OrderOutcome<&'a MappedMainOrder> {
@@ -248,6 +335,14 @@ impl<O> HoldOutcome<O> {
             Dislodged(o) => Dislodged(map_fn(o)),
         }
     }
+
+    /// The order that dislodged the holding unit, if any.
+    pub fn dislodger(&self) -> Option<&O> {
+        match self {
+            HoldOutcome::Succeeds => None,
+            HoldOutcome::Dislodged(o) => Some(o),
+        }
+    }
 }
 
 impl<O> From<&'_ HoldOutcome<O>> for OrderState {
@@ -274,21 +369,25 @@ pub enum AttackOutcome<O> {
     MoveToSelf,
     /// There was no way for the unit to reach the specified destination.
     ///
-    /// This usually indicates that a convoy was possible but either disrupted
-    /// or not ordered, as routes where there is no possibility of a path are
-    /// instead deemed illegal.
+    /// This usually indicates that a convoy was possible but not ordered, as routes
+    /// where there is no possibility of a path are instead deemed illegal. Moves whose
+    /// ordered convoys were disrupted are reported as [`AttackOutcome::ConvoyDisrupted`].
     NoPath,
+    /// The unit was ordered to travel by a convoy route, but enough of the convoying
+    /// fleets were dislodged or failed that no route remained.
+    ConvoyDisrupted,
     /// The unit tried to move into a province occupied by another unit of the
     /// same nation.
     FriendlyFire,
     /// The unit was prevented from entering the province by the specified order.
     Prevented(O),
-    /// The intended victim of the attack instead dislodged the attacker and did not use a convoy.
+    /// The intended victim of the attack, identified by the specified order, instead dislodged
+    /// the attacker and did not use a convoy.
     ///
     /// A unit that loses a head-to-head battle is dislodged, cannot retreat to the province from
     /// which it was attacked, and has no strength to prevent other units from occupying that
     /// province.
-    LostHeadToHead,
+    LostHeadToHead(O),
     /// The intended victim of the attack fended off the attacker, possibly with support from
     /// other units.
     OccupierDefended,
@@ -303,13 +402,28 @@ impl<O> AttackOutcome<O> {
         match self {
             MoveToSelf => MoveToSelf,
             NoPath => NoPath,
+            ConvoyDisrupted => ConvoyDisrupted,
             FriendlyFire => FriendlyFire,
             Prevented(p) => Prevented(map_fn(p)),
-            LostHeadToHead => LostHeadToHead,
+            LostHeadToHead(o) => LostHeadToHead(map_fn(o)),
             OccupierDefended => OccupierDefended,
             Succeeds => Succeeds,
         }
     }
+
+    /// The order that dislodged the moving unit by winning a head-to-head battle with it.
+    ///
+    /// A unit whose move failed for another reason may still have been dislodged by a unit
+    /// entering the province it failed to leave; use [`Outcome::dislodged_positions`] or the
+    /// [retreat phase](super::retreat::Start::dislodged) to find every dislodged unit.
+    ///
+    /// [`Outcome::dislodged_positions`]: super::Outcome::dislodged_positions
+    pub fn dislodger(&self) -> Option<&O> {
+        match self {
+            AttackOutcome::LostHeadToHead(o) => Some(o),
+            _ => None,
+        }
+    }
 }
 
 impl<O> From<&'_ AttackOutcome<O>> for OrderState {
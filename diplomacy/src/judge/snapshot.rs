@@ -0,0 +1,79 @@
+//! A self-contained record of the inputs to a main-phase adjudication.
+
+#[cfg(feature = "std")]
+use super::Submission;
+use super::{IllegalOrderPolicy, MappedMainOrder, Rulebook};
+use crate::collections::HashMap;
+#[cfg(feature = "std")]
+use crate::geo::{map_by_name, standard_map, Map};
+use crate::geo::{ProvinceKey, RegionKey};
+use crate::{Nation, UnitPosition};
+#[cfg(feature = "std")]
+use alloc::string::ToString;
+use alloc::{string::String, vec::Vec};
+
+/// Everything needed to reproduce a main-phase adjudication: the map, the starting state,
+/// the orders, and the rules.
+///
+/// With the `serde` feature, a snapshot can be stored or attached to a bug report and later
+/// replayed with [`SubmissionSnapshot::to_submission`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SubmissionSnapshot {
+    /// The [name](Map::name) of the map. Only built-in maps, which can be found with
+    /// [`map_by_name`], are recognized.
+    pub map: String,
+    /// The units on the board at the start of the turn. If `None`, the units are inferred
+    /// from the orders.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub units: Option<Vec<UnitPosition<'static, RegionKey>>>,
+    /// The supply center ownership at the start of the turn, if known.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ownerships: Option<HashMap<ProvinceKey, Nation>>,
+    /// The orders as submitted, including any illegal orders.
+    pub orders: Vec<MappedMainOrder>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub illegal_order_policy: IllegalOrderPolicy,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rules: Rulebook,
+}
+
+#[cfg(feature = "std")]
+impl SubmissionSnapshot {
+    /// Create a snapshot of orders on the standard map, inferring the starting state from the
+    /// orders and using the default rules.
+    pub fn standard(orders: Vec<MappedMainOrder>) -> Self {
+        Self {
+            map: standard_map().name().to_string(),
+            units: None,
+            ownerships: None,
+            orders,
+            illegal_order_policy: IllegalOrderPolicy::default(),
+            rules: Rulebook::default(),
+        }
+    }
+
+    /// The map identified by the snapshot, or `None` if the map is not recognized.
+    pub fn world_map(&self) -> Option<&'static Map> {
+        map_by_name(&self.map)
+    }
+
+    /// Recreate the submission described by the snapshot, or `None` if the map is not
+    /// recognized. Adjudicate it with [`SubmissionSnapshot::rules`] to reproduce the outcome.
+    pub fn to_submission(&self) -> Option<Submission<'static>> {
+        let world_map = self.world_map()?;
+        let orders = self.orders.clone();
+        let submission = match (&self.units, &self.ownerships) {
+            (Some(units), Some(ownerships)) => {
+                Submission::with_state(world_map, units, ownerships.clone(), orders)
+            }
+            (Some(units), None) => Submission::new(world_map, units, orders),
+            (None, Some(ownerships)) => {
+                Submission::with_state(world_map, &self.orders, ownerships.clone(), orders)
+            }
+            (None, None) => Submission::with_inferred_state(world_map, orders),
+        };
+
+        Some(submission.with_illegal_order_policy(self.illegal_order_policy))
+    }
+}
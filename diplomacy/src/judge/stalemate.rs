@@ -0,0 +1,145 @@
+//! Checks for positions which orders cannot change, the basis of stalemate analysis.
+
+use super::{MappedMainOrder, Rulebook, Submission};
+use crate::geo::{Map, RegionKey};
+use crate::order::{MainCommand, MoveCommand, Order, SupportedOrder};
+use crate::{UnitPosition, UnitPositions};
+use alloc::{vec, vec::Vec};
+
+/// Whether adjudicating `orders` under the standard rules leaves every unit in `positions`
+/// where it started: no unit moves, and none is dislodged.
+///
+/// Units without orders hold, so with no orders this checks that the board is at rest.
+pub fn is_static(
+    world_map: &Map,
+    positions: &impl UnitPositions<RegionKey>,
+    orders: Vec<MappedMainOrder>,
+) -> bool {
+    let mut start = positions
+        .unit_positions()
+        .iter()
+        .map(UnitPosition::to_owned)
+        .collect::<Vec<_>>();
+    start.sort_by(|a, b| a.region.cmp(&b.region));
+
+    let submission = Submission::new(world_map, positions, orders);
+    let outcome = submission.adjudicate(Rulebook::default());
+    outcome.resulting_positions() == start
+}
+
+/// Whether the units given `defense` orders keep their provinces against every combination of
+/// orders the other units in `positions` could be given, under the standard rules.
+///
+/// Each other unit may hold, move into a province with a defending unit, or support another
+/// such move. Moving into a defending province is also how an attacker cuts support, so this
+/// covers attacks on the supports that hold a line together. Convoyed attacks are not
+/// considered.
+///
+/// Every combination of orders is adjudicated, so this is only practical for a handful of
+/// attacking units.
+pub fn is_stalemate_line(
+    world_map: &Map,
+    positions: &impl UnitPositions<RegionKey>,
+    defense: &[MappedMainOrder],
+) -> bool {
+    let defended = defense
+        .iter()
+        .map(|ord| ord.region.province())
+        .collect::<Vec<_>>();
+
+    let attackers = positions
+        .unit_positions()
+        .into_iter()
+        .filter(|pos| !defended.contains(&pos.region.province()))
+        .collect::<Vec<_>>();
+
+    let attacks = attackers
+        .iter()
+        .map(|pos| {
+            world_map
+                .borders_containing(pos.region)
+                .into_iter()
+                .filter(|b| b.is_passable_by(pos.unit.unit_type()))
+                .filter_map(|b| b.dest_from(pos.region))
+                .filter(|dest| defended.contains(&dest.province()))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let choices = attackers
+        .iter()
+        .enumerate()
+        .map(|(idx, pos)| {
+            let order = |command| {
+                Order::new(
+                    pos.nation().clone(),
+                    pos.unit.unit_type(),
+                    pos.region.clone(),
+                    command,
+                )
+            };
+
+            let mut choices = vec![order(MainCommand::Hold)];
+            for dest in &attacks[idx] {
+                choices.push(order(MainCommand::Move(MoveCommand::new((*dest).clone()))));
+            }
+
+            for (other, dests) in attackers.iter().zip(&attacks) {
+                if other == pos {
+                    continue;
+                }
+
+                for dest in dests {
+                    let can_reach = world_map
+                        .find_borders_between(pos.region, dest.province())
+                        .iter()
+                        .any(|b| b.is_passable_by(pos.unit.unit_type()));
+                    if can_reach {
+                        choices.push(order(MainCommand::Support(SupportedOrder::Move(
+                            other.unit.unit_type(),
+                            other.region.clone(),
+                            (*dest).clone(),
+                        ))));
+                    }
+                }
+            }
+
+            choices
+        })
+        .collect::<Vec<_>>();
+
+    // Visit every combination of choices, advancing the last attacker first like an odometer.
+    let mut selected = vec![0; attackers.len()];
+    loop {
+        let mut orders = defense.to_vec();
+        orders.extend(
+            selected
+                .iter()
+                .zip(&choices)
+                .map(|(&choice, orders)| orders[choice].clone()),
+        );
+
+        let submission = Submission::new(world_map, positions, orders);
+        let outcome = submission.adjudicate(Rulebook::default());
+        if outcome
+            .dislodged_positions()
+            .iter()
+            .any(|pos| defended.contains(&pos.region.province()))
+        {
+            return false;
+        }
+
+        let Some(idx) = selected
+            .iter()
+            .zip(&choices)
+            .rposition(|(&choice, orders)| choice + 1 < orders.len())
+        else {
+            return true;
+        };
+
+        selected[idx] += 1;
+        for choice in &mut selected[idx + 1..] {
+            *choice = 0;
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 pub trait Strength {
     /// Compute the strength of an action from its result.
     fn strength(&self) -> usize;
@@ -23,7 +25,7 @@ pub(crate) enum Prevent<O> {
     Prevents(O, Supporters<O>),
 }
 
-impl<O: std::fmt::Debug + Copy> Prevent<O> {
+impl<O: core::fmt::Debug + Copy> Prevent<O> {
     pub fn unwrap_order(&self) -> O {
         let Self::Prevents(order, _) = self else {
             panic!("Attempted to unwrap {:?} value", self);
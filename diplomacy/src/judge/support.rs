@@ -1,9 +1,49 @@
 //! Contains helper functions for evaluating the success of support commands
 //! during the main phase of a turn.
 
-use super::{calc, Adjudicate, Context, MappedMainOrder, OrderState, ResolverState};
-use crate::geo::Map;
-use crate::order::{Command, MainCommand, SupportedOrder};
+use super::{calc, convoy, Adjudicate, Context, MappedMainOrder, OrderState, ResolverState};
+use crate::geo::{Map, RegionKey};
+use crate::order::{Command, MainCommand, MoveCommand, SupportedOrder};
+use alloc::vec::Vec;
+
+/// Whether a supporting unit dislodged by the target of its supported attack still gives
+/// that support.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DislodgedSupport {
+    /// The support is cut, per DATC 6.D.17.
+    #[default]
+    Cut,
+    /// The support is not cut, because the target of an attack can never cut support for
+    /// that attack.
+    Sustained,
+}
+
+/// How a support order naming a destination without a coast matches a move to a specific
+/// coast of that province.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SupportCoast {
+    /// The support only matches a move whose destination is exactly the supported
+    /// destination. Correcting such orders is the caller's responsibility.
+    #[default]
+    Exact,
+    /// The support also matches a move to a coast of the supported destination if that is
+    /// the only coast of the province the moving unit could reach, per DATC 6.B.8.
+    InferUnambiguous,
+}
+
+/// Whether an army whose convoy was disrupted can cut support.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DisruptedConvoyCut {
+    /// The army needs a successful convoy to cut support, per DATC 6.F.6.
+    #[default]
+    DoesNotCut,
+    /// The army cuts support as long as it was ordered to be convoyed along a complete
+    /// route, even if a convoying fleet was dislodged.
+    Cuts,
+}
 
 fn order_cuts<'a>(
     ctx: &Context<'a, impl Adjudicate>,
@@ -32,7 +72,8 @@ fn order_cuts<'a>(
                 // Per http://uk.diplom.org/pouch/Zine/S2009M/Kruijswijk/DipMath_Chp5.htm
                 // we only resolve the cutting order in this precise case to minimize cycle
                 // risks.
-                !bool::from(resolver.resolve(ctx, cutting_order))
+                ctx.rules.policies().dislodged_support == DislodgedSupport::Sustained
+                    || !bool::from(resolver.resolve(ctx, cutting_order))
             }
             _ => false,
         };
@@ -45,6 +86,8 @@ fn order_cuts<'a>(
         // Therefore, we don't worry about units being convoyed that cut support on attacks against
         // their convoys; those situations will be handled by the cycle resolver.
         calc::path_exists(ctx, resolver, cutting_order)
+            || (ctx.rules.policies().disrupted_convoy_cut == DisruptedConvoyCut::Cuts
+                && convoy::ordered_route_exists(ctx, cutting_order))
     } else {
         false
     }
@@ -60,6 +103,21 @@ pub fn find_cutting_order<'a>(
         .find(|order| order_cuts(ctx, resolver, support_order, order))
 }
 
+/// Find every order which cuts a specified support order, in the order they were submitted.
+///
+/// Unlike [`find_cutting_order`], this does not stop at the first hit. Attacks by the
+/// supporter's countrymen never cut support, and an attack from the province the support is
+/// directed against only cuts support if it dislodges the supporter.
+pub fn cut_support_sources<'a>(
+    ctx: &Context<'a, impl Adjudicate>,
+    resolver: &mut ResolverState<'a>,
+    support_order: &MappedMainOrder,
+) -> Vec<&'a MappedMainOrder> {
+    ctx.orders()
+        .filter(|order| order_cuts(ctx, resolver, support_order, order))
+        .collect()
+}
+
 /// A SUPPORT decision of a unit ordered to support results in 'cut' when:
 /// At least one of the units ordered to move to the area of the supporting unit
 /// has a minimum ATTACK STRENGTH of one or more. Again, if the support order is
@@ -107,6 +165,35 @@ pub fn can_reach(world_map: &Map, support_order: &MappedMainOrder) -> bool {
     }
 }
 
+/// Determines if a support order could ever help the order it supports, based only on
+/// the map. This is false if the supporter cannot reach the province where support is
+/// needed (DATC 6.A.10, 6.B.5) or if it supports an action in its own province, and true
+/// for support into a coast the supporter cannot reach itself (DATC 6.B.4).
+///
+/// Orders which are not supports are never able to support.
+pub fn can_support(world_map: &Map, support_order: &MappedMainOrder) -> bool {
+    is_legal(support_order) && can_reach(world_map, support_order)
+}
+
+/// Determines if the order a support names could itself be legal, based only on the map.
+/// A support for a move the supported unit can never make - such as a fleet moving to a
+/// coast it does not border (DATC 6.D.23) or an army moving to a sea (DATC 6.D.24) - is
+/// void, because the supported move is illegal and the unit holds instead.
+///
+/// Supports for holds are always valid here, and orders which are not supports never are.
+pub fn support_is_valid_for_target(world_map: &Map, support_order: &MappedMainOrder) -> bool {
+    match &support_order.command {
+        MainCommand::Support(SupportedOrder::Hold(..)) => true,
+        MainCommand::Support(supported @ SupportedOrder::Move(unit_type, from, to)) => {
+            supported.is_legal()
+                && MainCommand::Move(MoveCommand::new(to.clone()))
+                    .validate(world_map, from, *unit_type)
+                    .is_ok()
+        }
+        _ => false,
+    }
+}
+
 /// Returns true if an order is a legal support order.
 fn is_legal(support_order: &MappedMainOrder) -> bool {
     use crate::order::MainCommand::*;
@@ -130,18 +217,63 @@ pub fn is_successful<'a>(
     resolver: &mut ResolverState<'a>,
     supported: &MappedMainOrder,
     support_order: &'a MappedMainOrder,
+) -> bool {
+    is_support_for(ctx, support_order, supported)
+        && can_reach(ctx.world_map, support_order)
+        && resolver.resolve(ctx, support_order).into()
+}
+
+/// Returns true if `support_order` is a legal support naming `supported`, without checking
+/// whether the support reaches or is cut.
+pub fn is_support_for(
+    ctx: &Context<'_, impl Adjudicate>,
+    support_order: &MappedMainOrder,
+    supported: &MappedMainOrder,
 ) -> bool {
     if let MainCommand::Support(ref beneficiary) = support_order.command {
         is_legal(support_order)
             && beneficiary.is_legal()
-            && beneficiary == supported
-            && can_reach(ctx.world_map, support_order)
-            && resolver.resolve(ctx, support_order).into()
+            && (beneficiary == supported
+                || (ctx.rules.policies().support_coast == SupportCoast::InferUnambiguous
+                    && infers_coast(ctx.world_map, beneficiary, supported)))
     } else {
         false
     }
 }
 
+/// Checks if `beneficiary` names the destination of `supported` without its coast, and
+/// that coast is the only one in the province which the moving unit could reach.
+fn infers_coast(
+    world_map: &Map,
+    beneficiary: &SupportedOrder<RegionKey>,
+    supported: &MappedMainOrder,
+) -> bool {
+    let SupportedOrder::Move(unit_type, from, to) = beneficiary else {
+        return false;
+    };
+
+    let Some(dest) = supported.move_dest() else {
+        return false;
+    };
+
+    if to.coast().is_some()
+        || dest.coast().is_none()
+        || *unit_type != supported.unit_type
+        || *from != supported.region
+        || dest.province() != to.province()
+    {
+        return false;
+    }
+
+    let mut reachable = world_map
+        .find_borders_between(from, to.province())
+        .into_iter()
+        .filter(|b| b.is_passable_by(*unit_type))
+        .filter_map(|b| b.dest_from(from));
+
+    reachable.next() == Some(dest) && reachable.next().is_none()
+}
+
 /// Finds all successful orders which support a given order.
 pub fn find_for<'a>(
     ctx: &Context<'a, impl Adjudicate>,
@@ -153,6 +285,23 @@ pub fn find_for<'a>(
         .collect()
 }
 
+/// Whether a support order is trying to keep a unit in place or help it move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SupportKind {
+    Hold,
+    Move,
+}
+
+impl<L> From<&'_ SupportedOrder<L>> for SupportKind {
+    fn from(supported: &SupportedOrder<L>) -> Self {
+        match supported {
+            SupportedOrder::Hold(..) => SupportKind::Hold,
+            SupportedOrder::Move(..) => SupportKind::Move,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SupportOutcome<O> {
@@ -206,7 +355,7 @@ mod test {
     use crate::order::{MainCommand, MoveCommand, Order, SupportedOrder};
     use crate::Nation;
     use crate::UnitType;
-    use std::str::FromStr;
+    use core::str::FromStr;
 
     fn reg(s: &str) -> RegionKey {
         RegionKey::from_str(s).unwrap()
@@ -234,7 +383,7 @@ mod test {
         assert_eq!(supp_com, orders[1]);
         assert!(super::can_reach(standard_map(), &orders[0]));
 
-        let resolver_ctx = Context::new(standard_map(), crate::judge::Rulebook, &orders);
+        let resolver_ctx = Context::new(standard_map(), crate::judge::Rulebook::default(), &orders);
         let mut res_state = ResolverState::new();
         let supporters = find_for(&resolver_ctx, &mut res_state, &orders[1]);
         assert!(!supporters.is_empty());
@@ -245,7 +394,7 @@ mod test {
         let fra = Nation::from("fra");
         let spa_nc = RegionKey::from_str("spa(nc)").unwrap();
         let supp_com = SupportedOrder::Move(UnitType::Fleet, reg("gas"), spa_nc.clone());
-        let orders = vec![
+        let orders = [
             Order::new(
                 fra.clone(),
                 UnitType::Fleet,
@@ -264,4 +413,70 @@ mod test {
         assert_eq!(supp_com, orders[0]);
         assert!(super::can_reach(standard_map(), &orders[1]));
     }
+
+    #[test]
+    fn can_support_on_standard_map() {
+        let can_support = |order: &str| {
+            super::can_support(standard_map(), &order.parse::<MappedMainOrder>().unwrap())
+        };
+
+        // DATC 6.A.10
+        assert!(!can_support("ITA: F rom Supports A apu -> ven"));
+        // DATC 6.B.4
+        assert!(can_support("FRA: F mar Supports F gas -> spa(nc)"));
+        // DATC 6.B.5
+        assert!(!can_support("FRA: F spa(nc) Supports F mar -> lyo"));
+        // DATC 6.D.34
+        assert!(!can_support("ITA: A tyr Supports A ven -> tyr"));
+        assert!(can_support("FRA: A bur Supports A par"));
+        assert!(!can_support("FRA: A bur -> par"));
+    }
+
+    #[test]
+    fn support_is_valid_for_target_on_standard_map() {
+        let is_valid = |order: &str| {
+            super::support_is_valid_for_target(
+                standard_map(),
+                &order.parse::<MappedMainOrder>().unwrap(),
+            )
+        };
+
+        // DATC 6.D.23
+        assert!(is_valid("ITA: F wes Supports F lyo -> spa(sc)"));
+        assert!(!is_valid("FRA: F mar Supports F spa(nc) -> lyo"));
+        // DATC 6.D.24
+        assert!(!is_valid("FRA: F spa(sc) Supports A mar -> lyo"));
+        assert!(is_valid("ENG: F nwg Supports A lon -> nwy"));
+        assert!(is_valid("FRA: A bur Supports A par"));
+        assert!(!is_valid("FRA: A bur -> par"));
+    }
+
+    /// DATC 6.D.15, with an additional Turkish attack on Constantinople from Smyrna.
+    #[test]
+    fn cut_support_sources_excludes_supported_target() {
+        let orders = [
+            "RUS: F con Supports F bla -> ank",
+            "RUS: F bla -> ank",
+            "TUR: F ank -> con",
+            "TUR: A smy -> con",
+        ]
+        .into_iter()
+        .map(|o| o.parse::<MappedMainOrder>().unwrap())
+        .collect::<Vec<_>>();
+
+        let ctx = Context::new(standard_map(), crate::judge::Rulebook::default(), &orders);
+        let mut resolver = ResolverState::new();
+        assert_eq!(
+            cut_support_sources(&ctx, &mut resolver, &orders[0]),
+            vec![&orders[3]]
+        );
+
+        let ctx = Context::new(
+            standard_map(),
+            crate::judge::Rulebook::default(),
+            &orders[..3],
+        );
+        let mut resolver = ResolverState::new();
+        assert!(cut_support_sources(&ctx, &mut resolver, &orders[0]).is_empty());
+    }
 }
@@ -0,0 +1,80 @@
+//! An adjudicator decorator which records the work done during resolution.
+
+use super::{
+    Adjudicate, Context, MappedMainOrder, OrderOutcome, OrderState, ResolverState, Rulebook,
+};
+use alloc::vec::Vec;
+use core::cell::{Ref, RefCell};
+
+/// A single call to [`Adjudicate::adjudicate`] made during resolution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry {
+    /// The order that was adjudicated.
+    pub order: MappedMainOrder,
+    /// The result of adjudicating the order given the resolver's guesses at the time.
+    pub result: OrderState,
+}
+
+/// Wraps an adjudicator and records every call the resolver makes to it, in call order.
+///
+/// The resolver adjudicates an order each time it guesses at the order's outcome, so the
+/// log shows the guesses the resolver made and what each guess led to. This is intended
+/// for teaching and debugging the resolution algorithm; it is not needed to judge a turn.
+#[derive(Debug, Default)]
+pub struct Tracing<A> {
+    inner: A,
+    log: RefCell<Vec<TraceEntry>>,
+}
+
+impl<A: Adjudicate> Tracing<A> {
+    /// Wrap an adjudicator to trace its calls.
+    pub fn new(inner: A) -> Self {
+        Self {
+            inner,
+            log: RefCell::default(),
+        }
+    }
+
+    /// The wrapped adjudicator.
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// The calls recorded so far, in the order they were made.
+    pub fn entries(&self) -> Ref<'_, Vec<TraceEntry>> {
+        self.log.borrow()
+    }
+}
+
+impl<A: Adjudicate> Adjudicate for Tracing<A> {
+    fn adjudicate<'a>(
+        &self,
+        context: &Context<'a, impl Adjudicate>,
+        resolver: &mut ResolverState<'a>,
+        order: &'a MappedMainOrder,
+    ) -> OrderState {
+        let result = self.inner.adjudicate(context, resolver, order);
+        self.log.borrow_mut().push(TraceEntry {
+            order: order.clone(),
+            result,
+        });
+        result
+    }
+
+    fn explain<'a>(
+        &self,
+        context: &Context<'a, impl Adjudicate>,
+        resolver: &mut ResolverState<'a>,
+        order: &'a MappedMainOrder,
+    ) -> OrderOutcome<&'a MappedMainOrder> {
+        self.inner.explain(context, resolver, order)
+    }
+
+    fn policies(&self) -> &Rulebook {
+        self.inner.policies()
+    }
+
+    fn defense_bonus(&self, order: &MappedMainOrder) -> usize {
+        self.inner.defense_bonus(order)
+    }
+}
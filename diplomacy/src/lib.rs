@@ -1,7 +1,26 @@
 //! An adjudicator for orders in the board game Diplomacy. This adjudicator will
 //! be fully compatible with the [Diplomacy Adjudicator Test Cases](https://webdiplomacy.net/doc/DATC_v3_0.html).
+//!
+//! # Features
+//! - `std` (default): Use the standard library. Without it the crate is `no_std` and only
+//!   requires `alloc`; the built-in standard map, graph export, and reading maps from
+//!   [`std::io`] sources are unavailable.
+//! - `serde`: Serialization support for orders, map types, and outcomes.
+//! - `parallel`: Resolve independent parts of a turn on multiple threads. Implies `std`.
+//! - `dependency-graph`: Record the order dependencies consulted during adjudication.
+//! - `resolution-stats`: Count how often each order is resolved and how many guesses
+//!   adjudication makes, to find expensive positions.
+//! - `testing`: A harness for checking an [`Adjudicate`](judge::Adjudicate) implementation
+//!   against known cases, such as the DATC.
+//! - `check-invariants`: In debug builds, panic if an adjudication violates the properties
+//!   checked by [`Outcome::check_invariants`](judge::Outcome::check_invariants).
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 pub mod calendar;
+mod collections;
 pub mod geo;
 pub mod judge;
 mod nation;
@@ -16,10 +35,10 @@ pub use crate::nation::Nation;
 #[doc(inline)]
 pub use crate::order::{Command, Order};
 pub use crate::time::{Phase, Season, Time};
-pub use crate::unit::{Unit, UnitPosition, UnitPositions, UnitType};
+pub use crate::unit::{StartingPositions, Unit, UnitPosition, UnitPositions, UnitType};
 
 /// Format trait for short naming of objects in orders.
 pub trait ShortName {
     /// This method returns the short display name of the object.
-    fn short_name(&self) -> std::borrow::Cow<'_, str>;
+    fn short_name(&self) -> alloc::borrow::Cow<'_, str>;
 }
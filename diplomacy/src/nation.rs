@@ -1,6 +1,7 @@
 use crate::ShortName;
-use std::borrow::Cow;
-use std::fmt;
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::fmt;
 
 /// An actor in the game. Nations can own units and issue orders.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -19,7 +20,7 @@ impl fmt::Display for Nation {
     }
 }
 
-impl<'a> From<&'a str> for Nation {
+impl From<&str> for Nation {
     fn from(s: &str) -> Self {
         Nation(String::from(s))
     }
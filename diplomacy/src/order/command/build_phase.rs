@@ -1,8 +1,11 @@
 use super::Command;
 use crate::geo::Location;
-use std::fmt;
+use core::fmt;
 
 /// A command issued during the build/disband turn (typically "Winter").
+///
+/// Each command displays as the keyword the parser accepts for it, so a displayed build order
+/// such as `GER: A war build` parses back into an equal order. New commands must preserve this.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BuildCommand {
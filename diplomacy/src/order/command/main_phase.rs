@@ -3,8 +3,8 @@ use crate::geo::Location;
 use crate::order::Order;
 use crate::ShortName;
 use crate::UnitType;
-use std::cmp::PartialEq;
-use std::fmt;
+use core::cmp::PartialEq;
+use core::fmt;
 
 pub type MainOrder<L> = Order<L, MainCommand<L>>;
 
@@ -25,6 +25,45 @@ pub enum MainCommand<L> {
     Convoy(ConvoyedMove<L>),
 }
 
+impl<L> MainCommand<L> {
+    /// Convert each location in the command using `map_fn`, stopping at the first error.
+    pub fn try_map_location<M, E>(
+        &self,
+        mut map_fn: impl FnMut(&L) -> Result<M, E>,
+    ) -> Result<MainCommand<M>, E> {
+        Ok(match self {
+            MainCommand::Hold => MainCommand::Hold,
+            MainCommand::Move(cmd) => MainCommand::Move(cmd.try_map_location(&mut map_fn)?),
+            MainCommand::Support(sup) => MainCommand::Support(sup.try_map_location(&mut map_fn)?),
+            MainCommand::Convoy(cm) => MainCommand::Convoy(cm.try_map_location(&mut map_fn)?),
+        })
+    }
+
+    /// The origin and destination of the army a convoy command is carrying, or `None` if the
+    /// command is not a convoy.
+    pub fn convoyed_move(&self) -> Option<(&L, &L)> {
+        match self {
+            MainCommand::Convoy(cm) => Some((cm.from(), cm.to())),
+            _ => None,
+        }
+    }
+}
+
+impl<L: Location> MainCommand<L> {
+    /// The province this command tries to take, or helps another unit take.
+    ///
+    /// This is the destination of a move, a supported move, or a convoyed move. Holds and
+    /// supports to hold do not target a province.
+    pub fn targets_province(&self) -> Option<&L::Province> {
+        match self {
+            MainCommand::Hold | MainCommand::Support(SupportedOrder::Hold(..)) => None,
+            MainCommand::Move(cmd) => Some(cmd.dest().province()),
+            MainCommand::Support(SupportedOrder::Move(_, _, dest)) => Some(dest.province()),
+            MainCommand::Convoy(cm) => Some(cm.to().province()),
+        }
+    }
+}
+
 impl<L: Location> Command<L> for MainCommand<L> {
     fn move_dest(&self) -> Option<&L> {
         match *self {
@@ -50,34 +89,39 @@ impl<L: Location> fmt::Display for MainCommand<L> {
     }
 }
 
-/// Validate that the deserialized value is not `Some(false)`.
-#[cfg(feature = "serde")]
-fn deserialize_true<'de, D: serde::Deserializer<'de>>(de: D) -> Result<Option<bool>, D::Error> {
-    let value = <Option<bool> as serde::Deserialize>::deserialize(de)?;
-    if matches!(value, Some(false)) {
-        return Err(serde::de::Error::custom("field must be true or null"));
-    }
-
-    Ok(value)
-}
-
 /// A move command with a destination and an optional convoy specification.
+///
+/// # Convoy Intent
+/// A move order can state whether the unit intends to travel by convoy:
+///
+/// ```
+/// use diplomacy::geo::RegionKey;
+/// use diplomacy::order::MoveCommand;
+///
+/// // A plain move doesn't express a preference; the rulebook decides whether a convoy is used.
+/// let plain = MoveCommand::new(RegionKey::new("bel", None));
+/// assert_eq!(plain.is_convoy_intended(), None);
+/// assert!(!plain.mentions_convoy());
+///
+/// // A move "via convoy" will not consider direct routes to the destination.
+/// let via_convoy = MoveCommand::with_mandatory_convoy(RegionKey::new("bel", None));
+/// assert_eq!(via_convoy.is_convoy_intended(), Some(true));
+/// assert!(via_convoy.mandates_convoy());
+///
+/// // A move "via land" will not consider convoy routes to the destination.
+/// let via_land = MoveCommand::with_forbidden_convoy(RegionKey::new("bel", None));
+/// assert_eq!(via_land.is_convoy_intended(), Some(false));
+/// assert!(via_land.forbids_convoy());
+/// ```
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MoveCommand<L> {
     dest: L,
     #[cfg_attr(
         feature = "serde",
-        serde(
-            default,
-            skip_serializing_if = "Option::is_none",
-            deserialize_with = "deserialize_true"
-        )
+        serde(default, skip_serializing_if = "Option::is_none")
     )]
     /// Whether the order required, forbade, or didn't specify convoy usage.
-    ///
-    /// Right now, the adjudicator doesn't handle the forbid case, so there's no way to construct
-    /// such a command.
     use_convoy: Option<bool>,
 }
 
@@ -98,6 +142,14 @@ impl<L> MoveCommand<L> {
         }
     }
 
+    /// Create a new move command which forbids the use of a convoy, even if one is available.
+    pub fn with_forbidden_convoy(dest: L) -> Self {
+        Self {
+            dest,
+            use_convoy: Some(false),
+        }
+    }
+
     /// Get the move command's destination region.
     pub fn dest(&self) -> &L {
         &self.dest
@@ -113,10 +165,71 @@ impl<L> MoveCommand<L> {
         self.use_convoy == Some(true)
     }
 
+    /// The order explicitly forbids the use of a convoy. If `true`, convoy routes to the
+    /// destination should not be considered when choosing a path.
+    pub fn forbids_convoy(&self) -> bool {
+        self.use_convoy == Some(false)
+    }
+
     /// The order explicitly mentions convoys, either mandating or forbidding their use.
     pub fn mentions_convoy(&self) -> bool {
         self.use_convoy.is_some()
     }
+
+    /// The convoy intent stated by the order: `Some(true)` if the order mandates a convoy,
+    /// `Some(false)` if the order forbids one, and `None` if the order doesn't say.
+    ///
+    /// This is [`convoy_intent`](Self::convoy_intent) as an `Option<bool>`.
+    pub fn is_convoy_intended(&self) -> Option<bool> {
+        match self.convoy_intent() {
+            ConvoyIntent::None => None,
+            ConvoyIntent::Forbidden => Some(false),
+            ConvoyIntent::Mandated => Some(true),
+        }
+    }
+
+    /// The convoy intent stated by the order.
+    ///
+    /// This only reflects the text of the order; some rulebooks also infer intent from other
+    /// orders, such as a fleet of the same nation ordered to convoy the unit.
+    pub fn convoy_intent(&self) -> ConvoyIntent {
+        match self.use_convoy {
+            None => ConvoyIntent::None,
+            Some(false) => ConvoyIntent::Forbidden,
+            Some(true) => ConvoyIntent::Mandated,
+        }
+    }
+
+    /// Convert the destination using `map_fn`, keeping the convoy preference.
+    pub fn try_map_location<M, E>(
+        &self,
+        mut map_fn: impl FnMut(&L) -> Result<M, E>,
+    ) -> Result<MoveCommand<M>, E> {
+        Ok(MoveCommand {
+            dest: map_fn(&self.dest)?,
+            use_convoy: self.use_convoy,
+        })
+    }
+}
+
+/// How the text of a move order says convoys should be used to reach its destination.
+///
+/// There is no single "mentioned" state: an order can only mention convoys by mandating one
+/// ("via convoy") or forbidding one ("via land"), and rulebooks treat those oppositely, so
+/// each has its own variant. [`MoveCommand::mentions_convoy`] is `true` for both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConvoyIntent {
+    /// The order does not mention convoys. The unit moves directly if it can, and otherwise
+    /// uses a convoy route if one exists.
+    #[default]
+    None,
+    /// The order was given "via land". Convoy routes are not considered, even if the unit
+    /// cannot move directly.
+    Forbidden,
+    /// The order was given "via convoy". The unit only moves along a convoy route, even if
+    /// it is adjacent to its destination (DATC 6.G.8).
+    Mandated,
 }
 
 impl<L: Location> From<MoveCommand<L>> for MainCommand<L> {
@@ -140,7 +253,7 @@ impl<L: Location> fmt::Display for MoveCommand<L> {
         self.dest.short_name().fmt(f)?;
         match self.use_convoy {
             Some(true) => write!(f, " via convoy"),
-            Some(false) => write!(f, " no convoy"),
+            Some(false) => write!(f, " via land"),
             None => Ok(()),
         }
     }
@@ -168,6 +281,19 @@ impl<L: Location> SupportedOrder<L> {
     }
 }
 
+impl<L> SupportedOrder<L> {
+    /// Convert each location in the supported order using `map_fn`, stopping at the first error.
+    pub fn try_map_location<M, E>(
+        &self,
+        mut map_fn: impl FnMut(&L) -> Result<M, E>,
+    ) -> Result<SupportedOrder<M>, E> {
+        Ok(match self {
+            SupportedOrder::Hold(ut, region) => SupportedOrder::Hold(*ut, map_fn(region)?),
+            SupportedOrder::Move(ut, fr, to) => SupportedOrder::Move(*ut, map_fn(fr)?, map_fn(to)?),
+        })
+    }
+}
+
 impl<L: ShortName> fmt::Display for SupportedOrder<L> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -226,6 +352,14 @@ impl<L> ConvoyedMove<L> {
     pub fn to(&self) -> &L {
         &self.1
     }
+
+    /// Convert both locations using `map_fn`, stopping at the first error.
+    pub fn try_map_location<M, E>(
+        &self,
+        mut map_fn: impl FnMut(&L) -> Result<M, E>,
+    ) -> Result<ConvoyedMove<M>, E> {
+        Ok(ConvoyedMove(map_fn(&self.0)?, map_fn(&self.1)?))
+    }
 }
 
 impl<L: ShortName> fmt::Display for ConvoyedMove<L> {
@@ -1,13 +1,13 @@
 use crate::geo::Location;
 
-use std::fmt;
+use core::fmt;
 
 mod build_phase;
 mod main_phase;
 mod retreat_phase;
 
 pub use self::build_phase::BuildCommand;
-pub use self::main_phase::{ConvoyedMove, MainCommand, MoveCommand, SupportedOrder};
+pub use self::main_phase::{ConvoyIntent, ConvoyedMove, MainCommand, MoveCommand, SupportedOrder};
 pub use self::retreat_phase::RetreatCommand;
 
 /// A command issued to a unit or location which uses a single concrete location type of `L`.
@@ -1,20 +1,38 @@
 use super::Command;
 use crate::{geo::Location, ShortName};
-use std::fmt;
+use core::fmt;
 
 /// Valid commands for the retreat phase of a turn.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RetreatCommand<L> {
+    /// The unit does not retreat, and is disbanded.
     Hold,
+    /// The unit retreats to the given region.
     Move(L),
+    /// The unit is explicitly disbanded.
+    Disband,
+}
+
+impl<L> RetreatCommand<L> {
+    /// Convert the destination, if any, using `map_fn`.
+    pub fn try_map_location<M, E>(
+        &self,
+        mut map_fn: impl FnMut(&L) -> Result<M, E>,
+    ) -> Result<RetreatCommand<M>, E> {
+        Ok(match self {
+            RetreatCommand::Hold => RetreatCommand::Hold,
+            RetreatCommand::Move(dest) => RetreatCommand::Move(map_fn(dest)?),
+            RetreatCommand::Disband => RetreatCommand::Disband,
+        })
+    }
 }
 
 impl<L: Location> Command<L> for RetreatCommand<L> {
     fn move_dest(&self) -> Option<&L> {
         match *self {
             RetreatCommand::Move(ref dst) => Some(dst),
-            RetreatCommand::Hold => None,
+            RetreatCommand::Hold | RetreatCommand::Disband => None,
         }
     }
 }
@@ -24,6 +42,7 @@ impl<L: ShortName> fmt::Display for RetreatCommand<L> {
         match self {
             RetreatCommand::Hold => write!(f, "hold"),
             RetreatCommand::Move(ref region) => write!(f, "-> {}", region.short_name()),
+            RetreatCommand::Disband => write!(f, "disband"),
         }
     }
 }
@@ -1,14 +1,28 @@
 //! The model for an order issued to a unit.
 
-use crate::{geo::Location, Nation, ShortName, Unit, UnitPosition, UnitType};
-use std::{borrow::Cow, fmt};
+use crate::geo::{Location, Map, RegionKey, Terrain};
+use crate::{Nation, ShortName, Unit, UnitPosition, UnitType};
+use alloc::borrow::Cow;
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::fmt;
 
 mod command;
 pub use self::command::{
-    BuildCommand, Command, ConvoyedMove, MainCommand, MoveCommand, RetreatCommand, SupportedOrder,
+    BuildCommand, Command, ConvoyIntent, ConvoyedMove, MainCommand, MoveCommand, RetreatCommand,
+    SupportedOrder,
 };
 
 /// An order is issued by a nation and gives a command to a unit in a region.
+///
+/// # Ordering
+/// Orders sort by nation, then region, then unit type, then command, each compared by its
+/// own ordering: nations and regions sort alphabetically by short name, and commands sort
+/// by kind (hold, move, support, convoy) and then by the locations they name. No two
+/// distinct orders compare as equal, so sorting a set of orders always yields the same
+/// sequence. This ordering carries no meaning for adjudication.
 #[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Order<L: Location, C: Command<L>> {
@@ -64,6 +78,198 @@ impl<L: Location, C: Command<L>> Order<L, C> {
     }
 }
 
+impl<C: Command<RegionKey>> Order<RegionKey, C> {
+    /// Create a new order, choosing the unit type based on the terrain of `region`.
+    ///
+    /// Armies are the only units that can occupy land regions and fleets are the only units
+    /// that can occupy sea regions, so the unit type is implied by those regions.
+    ///
+    /// # Errors
+    /// Coastal regions can be occupied by either type of unit, so orders to those regions
+    /// return [`InferUnitTypeError::Ambiguous`]; use [`Order::new`] to specify the unit type
+    /// instead. Note that the named coasts of provinces such as Spain are sea regions, so
+    /// orders to `spa(nc)` infer a fleet while orders to `spa` infer an army.
+    pub fn infer(
+        map: &Map,
+        nation: Nation,
+        region: RegionKey,
+        command: C,
+    ) -> Result<Self, InferUnitTypeError> {
+        let unit_type = match map
+            .find_region(&region.short_name())
+            .ok_or(InferUnitTypeError::RegionNotFound)?
+            .terrain()
+        {
+            Terrain::Land => UnitType::Army,
+            Terrain::Sea => UnitType::Fleet,
+            Terrain::Coast => return Err(InferUnitTypeError::Ambiguous),
+        };
+
+        Ok(Order::new(nation, unit_type, region, command))
+    }
+}
+
+/// A way of writing an order as text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DisplayStyle {
+    /// The form produced by `Display` and accepted by the parser, such as
+    /// `FRA: A par -> bur`.
+    #[default]
+    Canonical,
+    /// The uppercase notation used by judges such as NJUDGE, such as `A PAR - BUR`.
+    ///
+    /// Judges list orders by power, so the nation is omitted. Coasts are written as
+    /// `STP/SC`, and commands are abbreviated: `H` for hold, `-` for move, `S` for
+    /// support, and `C` for convoy. Judges have no notation for a move's convoy
+    /// preference, so that is omitted as well.
+    Judge,
+}
+
+impl<L: Location> MainOrder<L> {
+    /// Convert every location in the order using `map_fn`, stopping at the first error.
+    ///
+    /// This allows orders to be parsed structurally and then resolved against a specific map,
+    /// such as to reject regions the map does not contain.
+    pub fn try_map_region<M: Location, E>(
+        &self,
+        mut map_fn: impl FnMut(&L) -> Result<M, E>,
+    ) -> Result<MainOrder<M>, E> {
+        Ok(Order::new(
+            self.nation.clone(),
+            self.unit_type,
+            map_fn(&self.region)?,
+            self.command.try_map_location(map_fn)?,
+        ))
+    }
+
+    /// How the order's text says convoys should be used to reach its destination.
+    ///
+    /// Orders which are not moves never mention convoys, and report [`ConvoyIntent::None`].
+    /// Some rulebooks also infer intent from other orders, such as a fleet of the same
+    /// nation ordered to convoy the unit; this only reflects the order itself.
+    pub fn declared_convoy_intent(&self) -> ConvoyIntent {
+        match &self.command {
+            MainCommand::Move(cmd) => cmd.convoy_intent(),
+            _ => ConvoyIntent::None,
+        }
+    }
+
+    /// Whether `self` and `other` give the same command to the same unit, ignoring whether a
+    /// move mandates or forbids convoys.
+    ///
+    /// Unlike `==`, this treats `A lon -> bel` and `A lon -> bel via convoy` as the same order,
+    /// which is useful when comparing order sets whose sources annotate intent differently.
+    pub fn same_action(&self, other: &Self) -> bool {
+        self.nation == other.nation
+            && self.unit_type == other.unit_type
+            && self.region == other.region
+            && match (&self.command, &other.command) {
+                (MainCommand::Move(a), MainCommand::Move(b)) => a.dest() == b.dest(),
+                (a, b) => a == b,
+            }
+    }
+
+    /// Write the order as text in the specified style.
+    ///
+    /// [`DisplayStyle::Canonical`] gives the same text as `Display`.
+    pub fn to_string_styled(&self, style: DisplayStyle) -> String {
+        match style {
+            DisplayStyle::Canonical => self.to_string(),
+            DisplayStyle::Judge => {
+                let unit = format!(
+                    "{} {}",
+                    self.unit_type.short_name().to_uppercase(),
+                    judge_location(&self.region)
+                );
+                match &self.command {
+                    MainCommand::Hold => format!("{} H", unit),
+                    MainCommand::Move(cmd) => format!("{} - {}", unit, judge_location(cmd.dest())),
+                    MainCommand::Support(SupportedOrder::Hold(ut, region)) => format!(
+                        "{} S {} {}",
+                        unit,
+                        ut.short_name().to_uppercase(),
+                        judge_location(region)
+                    ),
+                    MainCommand::Support(SupportedOrder::Move(ut, from, to)) => format!(
+                        "{} S {} {} - {}",
+                        unit,
+                        ut.short_name().to_uppercase(),
+                        judge_location(from),
+                        judge_location(to)
+                    ),
+                    MainCommand::Convoy(mv) => format!(
+                        "{} C A {} - {}",
+                        unit,
+                        judge_location(mv.from()),
+                        judge_location(mv.to())
+                    ),
+                }
+            }
+        }
+    }
+}
+
+/// Write a location in judge notation, such as `STP/SC` for `stp(sc)`.
+fn judge_location(location: &impl ShortName) -> String {
+    location
+        .short_name()
+        .to_uppercase()
+        .replace('(', "/")
+        .replace(')', "")
+}
+
+impl<L: Location> RetreatOrder<L> {
+    /// Convert every location in the order using `map_fn`, stopping at the first error.
+    pub fn try_map_region<M: Location, E>(
+        &self,
+        mut map_fn: impl FnMut(&L) -> Result<M, E>,
+    ) -> Result<RetreatOrder<M>, E> {
+        Ok(Order::new(
+            self.nation.clone(),
+            self.unit_type,
+            map_fn(&self.region)?,
+            self.command.try_map_location(map_fn)?,
+        ))
+    }
+}
+
+impl<L: Location> BuildOrder<L> {
+    /// Convert the order's region using `map_fn`.
+    pub fn try_map_region<M: Location, E>(
+        &self,
+        map_fn: impl FnOnce(&L) -> Result<M, E>,
+    ) -> Result<BuildOrder<M>, E> {
+        Ok(Order::new(
+            self.nation.clone(),
+            self.unit_type,
+            map_fn(&self.region)?,
+            self.command.clone(),
+        ))
+    }
+}
+
+/// Error returned when [`Order::infer`] cannot determine the unit type for an order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InferUnitTypeError {
+    /// The region does not exist in the map.
+    RegionNotFound,
+    /// The region can be occupied by either an army or a fleet.
+    Ambiguous,
+}
+
+impl fmt::Display for InferUnitTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InferUnitTypeError::RegionNotFound => write!(f, "region not found in map"),
+            InferUnitTypeError::Ambiguous => {
+                write!(f, "region can be occupied by either an army or a fleet")
+            }
+        }
+    }
+}
+
+impl core::error::Error for InferUnitTypeError {}
+
 impl<L: Location, C: Command<L>> Command<L> for Order<L, C> {
     fn move_dest(&self) -> Option<&L> {
         self.command.move_dest()
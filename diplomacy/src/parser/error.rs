@@ -1,5 +1,7 @@
-use std::error as err;
-use std::fmt;
+use crate::Phase;
+use alloc::{string::String, vec::Vec};
+use core::error as err;
+use core::fmt;
 
 /// The error type for order parsing.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -45,12 +47,19 @@ pub enum ErrorKind {
     UnknownCommand,
     BadCoast,
     /// The order was recognized as a move, but the destination could not be parsed.
-    /// Move commands must use the one-word destination code and may include "via convoy".
+    /// Move commands must use the one-word destination code and may include "via convoy"
+    /// or "via land".
     MalformedMove,
     MalformedRegion,
     MalformedSupport,
     MalformedConvoy,
     TooFewWords(usize),
+    /// The region is not in the map. The suggestions are the names of similar regions that
+    /// are in the map, most similar first.
+    UnknownRegion(Vec<String>),
+    /// The command belongs to another phase, and cannot be given in the phase whose order
+    /// type was being parsed, such as a build order parsed as a main-phase order.
+    WrongPhase(Phase),
 }
 
 impl fmt::Display for ErrorKind {
@@ -64,6 +73,21 @@ impl fmt::Display for ErrorKind {
             ErrorKind::MalformedConvoy => write!(f, "Malformed convoy command"),
             ErrorKind::MalformedMove => write!(f, "Malformed move command"),
             ErrorKind::TooFewWords(min) => write!(f, "Too few words, expected {}", min),
+            ErrorKind::WrongPhase(phase) => {
+                let phase = match phase {
+                    Phase::Main => "main",
+                    Phase::Retreat => "retreat",
+                    Phase::Build => "build",
+                };
+                write!(f, "Command not allowed in {} phase", phase)
+            }
+            ErrorKind::UnknownRegion(ref suggestions) => {
+                write!(f, "Unknown region")?;
+                if !suggestions.is_empty() {
+                    write!(f, "; did you mean {}?", suggestions.join(", "))?;
+                }
+                Ok(())
+            }
         }
     }
 }
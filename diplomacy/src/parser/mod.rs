@@ -8,20 +8,29 @@
 //!
 //! # Supported Commands
 //! 1. Hold: `hold` or `holds`
-//! 1. Move: `-> {Destination}`
+//! 1. Move: `-> {Destination} [via convoy|via land]`
 //! 1. Support: `supports {UnitType} {Region} [-> {Dest}]`
 //! 1. Convoy: `convoys {Region} -> {Dest}`
 //! 1. Build: `build` (this is non-idiomatic, but easier to parse)
 //! 1. Disband: `disband`
+//!
+//! Each phase has its own order type: [`MainOrder`] for the main phase, `RetreatOrder` for
+//! the retreat phase, and `BuildOrder` for the build phase. Parsing an order as the type of
+//! a phase in which its command cannot be given, such as a build parsed as a main-phase
+//! order, fails with [`ErrorKind::WrongPhase`].
+//!
+//! Main-phase orders written in the notation used by other judges can be read with
+//! [`parse_njudge`].
 
-use std::str::FromStr;
+use alloc::{format, string::ToString, vec, vec::Vec};
+use core::str::FromStr;
 
-use crate::geo::Location;
+use crate::geo::{Location, RegionKey};
 use crate::order::{
-    BuildCommand, Command, ConvoyedMove, MainCommand, MoveCommand, Order, RetreatCommand,
-    SupportedOrder,
+    BuildCommand, Command, ConvoyedMove, MainCommand, MainOrder, MoveCommand, Order,
+    RetreatCommand, SupportedOrder,
 };
-use crate::Nation;
+use crate::{Nation, Phase, UnitType};
 
 mod error;
 
@@ -60,6 +69,72 @@ impl<L: Location + FromStr<Err = Error>, C: Command<L> + FromWords<Err = Error>>
     }
 }
 
+/// Parse a main-phase order written in the notation used by judges such as NJUDGE, such as
+/// `ENGLAND: F LON - ENG` or `A PAR S A MAR - BUR`.
+///
+/// This accepts the forms produced by
+/// [`DisplayStyle::Judge`](crate::order::DisplayStyle::Judge) and the other forms commonly
+/// found in archived games:
+///
+/// 1. Input is case-insensitive, and coasts may be written as `STP/SC`.
+/// 1. Moves use `-`, and may also use `->`.
+/// 1. Holds, supports, and convoys may be written in full or as `H`, `S`, and `C`. The
+///    unit type of a convoyed army is optional.
+/// 1. The order may start with the issuing power, followed by a colon. Judges list orders
+///    by power, so if it is omitted, `nation` issues the order. The full names of the
+///    standard powers are converted to the short names used elsewhere in the crate, such as
+///    `ENG` for `ENGLAND`.
+///
+/// Judge notation has no way to state a move's convoy preference, so moves never have one.
+pub fn parse_njudge(nation: &Nation, s: &str) -> ParseResult<MainOrder<RegionKey>> {
+    let mut words = s.split_whitespace().peekable();
+
+    let nation = match words.peek() {
+        Some(word) if word.ends_with(':') => {
+            let name = word.trim_end_matches(':').to_uppercase();
+            words.next();
+            Nation::from(match &name[..] {
+                "AUSTRIA" => "AUS",
+                "ENGLAND" => "ENG",
+                "FRANCE" => "FRA",
+                "GERMANY" => "GER",
+                "ITALY" => "ITA",
+                "RUSSIA" => "RUS",
+                "TURKEY" => "TUR",
+                other => other,
+            })
+        }
+        _ => nation.clone(),
+    };
+
+    let mut canonical = vec![];
+    while let Some(word) = words.next() {
+        let word = word.to_lowercase();
+        canonical.push(match &word[..] {
+            "-" => "->".to_string(),
+            "h" | "hold" | "holds" => "hold".to_string(),
+            "s" | "support" | "supports" => "supports".to_string(),
+            "c" | "convoy" | "convoys" => {
+                // Convoys name the army being convoyed, but the canonical form does not.
+                if words.peek().is_some_and(|w| w.parse::<UnitType>().is_ok()) {
+                    words.next();
+                }
+                "convoys".to_string()
+            }
+            _ => match word.split_once('/') {
+                Some((province, coast)) => format!("{}({})", province, coast),
+                None => word,
+            },
+        });
+    }
+
+    if canonical.len() < 3 {
+        return Err(Error::new(ErrorKind::TooFewWords(3), s));
+    }
+
+    format!("{}: {}", nation, canonical.join(" ")).parse()
+}
+
 impl<L: Location + FromStr<Err = Error>> FromWords for MainCommand<L> {
     type Err = Error;
 
@@ -69,6 +144,7 @@ impl<L: Location + FromStr<Err = Error>> FromWords for MainCommand<L> {
             "->" => Ok(MoveCommand::from_words(&words[1..])?.into()),
             "supports" => Ok(SupportedOrder::from_words(&words[1..])?.into()),
             "convoys" => Ok(ConvoyedMove::from_words(&words[1..])?.into()),
+            cmd @ ("build" | "disband") => Err(Error::new(ErrorKind::WrongPhase(Phase::Main), cmd)),
             cmd => Err(Error::new(ErrorKind::UnknownCommand, cmd)),
         }
     }
@@ -79,12 +155,16 @@ impl<L: Location + FromStr<Err = Error>> FromWords for MoveCommand<L> {
 
     fn from_words(w: &[&str]) -> ParseResult<Self> {
         const CONVOY_CASINGS: [&str; 2] = ["convoy", "Convoy"];
+        const LAND_CASINGS: [&str; 2] = ["land", "Land"];
 
         match w.len() {
             1 => Ok(MoveCommand::new(w[0].parse()?)),
             3 if w[1] == "via" && CONVOY_CASINGS.contains(&w[2]) => {
                 Ok(MoveCommand::with_mandatory_convoy(w[0].parse()?))
             }
+            3 if w[1] == "via" && LAND_CASINGS.contains(&w[2]) => {
+                Ok(MoveCommand::with_forbidden_convoy(w[0].parse()?))
+            }
             _ => Err(Error::new(ErrorKind::MalformedMove, w.join(" "))),
         }
     }
@@ -127,6 +207,10 @@ impl<L: Location + FromStr<Err = Error>> FromWords for RetreatCommand<L> {
         match &w[0].to_lowercase()[..] {
             "hold" | "holds" => Ok(RetreatCommand::Hold),
             "->" => Ok(RetreatCommand::Move(w[1].parse()?)),
+            "disband" => Ok(RetreatCommand::Disband),
+            cmd @ ("build" | "supports" | "convoys") => {
+                Err(Error::new(ErrorKind::WrongPhase(Phase::Retreat), cmd))
+            }
             cmd => Err(Error::new(ErrorKind::UnknownCommand, cmd)),
         }
     }
@@ -139,6 +223,9 @@ impl FromWords for BuildCommand {
         match &w[0].to_lowercase()[..] {
             "build" => Ok(BuildCommand::Build),
             "disband" => Ok(BuildCommand::Disband),
+            cmd @ ("hold" | "holds" | "->" | "supports" | "convoys") => {
+                Err(Error::new(ErrorKind::WrongPhase(Phase::Build), cmd))
+            }
             cmd => Err(Error::new(ErrorKind::UnknownCommand, cmd)),
         }
     }
@@ -151,6 +238,8 @@ mod test {
     use crate::order::{MainCommand, Order};
 
     type OrderParseResult = Result<Order<RegionKey, MainCommand<RegionKey>>, Error>;
+    type RetreatParseResult = Result<Order<RegionKey, RetreatCommand<RegionKey>>, Error>;
+    type BuildParseResult = Result<Order<RegionKey, BuildCommand>, Error>;
 
     #[test]
     fn hold() {
@@ -179,4 +268,103 @@ mod test {
         let no_pref: OrderParseResult = "ENG: A Lon -> Bel".parse();
         assert_ne!(no_pref.unwrap(), order);
     }
+
+    #[test]
+    fn army_move_via_land() {
+        let m_order: OrderParseResult = "ENG: A Lon -> Bel via land".parse();
+        let order = m_order.unwrap();
+        assert_eq!(order.to_string(), "ENG: A Lon -> Bel via land");
+
+        let alt_casing: OrderParseResult = "ENG: A Lon -> Bel via Land".parse();
+        assert_eq!(alt_casing.unwrap(), order);
+
+        let via_convoy: OrderParseResult = "ENG: A Lon -> Bel via convoy".parse();
+        assert_ne!(via_convoy.unwrap(), order);
+    }
+
+    #[test]
+    fn build_orders() {
+        for ord in [
+            "GER: A war build",
+            "RUS: F stp(nc) build",
+            "RUS: F stp(sc) build",
+            "FRA: A par disband",
+            "ENG: F nth disband",
+        ] {
+            let order: BuildParseResult = ord.parse();
+            assert_eq!(order.unwrap().to_string(), ord);
+        }
+
+        let order: BuildParseResult = "GER: A war Build".parse();
+        assert_eq!(order.unwrap().command, BuildCommand::Build);
+
+        let order: BuildParseResult = "GER: A war waive".parse();
+        assert_eq!(order.unwrap_err().kind(), &ErrorKind::UnknownCommand);
+    }
+
+    #[test]
+    fn orders_from_wrong_phase() {
+        let order: OrderParseResult = "GER: A war build".parse();
+        assert_eq!(
+            order.unwrap_err().kind(),
+            &ErrorKind::WrongPhase(Phase::Main)
+        );
+
+        let order: RetreatParseResult = "GER: A war supports A sil".parse();
+        assert_eq!(
+            order.unwrap_err().kind(),
+            &ErrorKind::WrongPhase(Phase::Retreat)
+        );
+
+        let order: BuildParseResult = "GER: A war -> sil".parse();
+        assert_eq!(
+            order.unwrap_err().kind(),
+            &ErrorKind::WrongPhase(Phase::Build)
+        );
+    }
+
+    #[test]
+    fn njudge_orders() {
+        let eng = Nation::from("ENG");
+        for (judge, canonical) in [
+            ("ENGLAND: F LON - ENG", "ENG: F lon -> eng"),
+            ("A PAR S A MAR - BUR", "ENG: A par supports A mar -> bur"),
+            ("F NTH C A LON - NWY", "ENG: F nth convoys lon -> nwy"),
+            ("F NTH CONVOY LON - NWY", "ENG: F nth convoys lon -> nwy"),
+            ("Russia: F STP/SC - BOT", "RUS: F stp(sc) -> bot"),
+            ("A PAR H", "ENG: A par hold"),
+            ("F MAR S F SPA/NC", "ENG: F mar supports F spa(nc)"),
+        ] {
+            let expected: OrderParseResult = canonical.parse();
+            assert_eq!(
+                parse_njudge(&eng, judge),
+                Ok(expected.unwrap()),
+                "{}",
+                judge
+            );
+        }
+
+        assert_eq!(
+            parse_njudge(&eng, "A PAR").unwrap_err().kind(),
+            &ErrorKind::TooFewWords(3)
+        );
+        assert_eq!(
+            parse_njudge(&eng, "A PAR X BUR").unwrap_err().kind(),
+            &ErrorKind::UnknownCommand
+        );
+    }
+
+    #[test]
+    fn retreat_orders() {
+        let order: RetreatParseResult = "TUR: F ank -> bla".parse();
+        assert_eq!(
+            order.unwrap().command,
+            RetreatCommand::Move(RegionKey::new("bla", None))
+        );
+
+        for ord in ["TUR: F ank -> bla", "TUR: F ank hold", "TUR: F ank disband"] {
+            let order: RetreatParseResult = ord.parse();
+            assert_eq!(order.unwrap().to_string(), ord);
+        }
+    }
 }
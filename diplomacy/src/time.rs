@@ -1,19 +1,50 @@
 use crate::ShortName;
+use alloc::borrow::Cow;
+use alloc::format;
+use core::str::FromStr;
 #[cfg(feature = "serde")]
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
-use std::borrow::Cow;
-use std::str::FromStr;
 
 /// The step in a current season. Not all seasons will have all steps.
+///
+/// Each phase takes its own type of order, so orders for one phase cannot be adjudicated in
+/// another. Parsing an order as the wrong phase's type fails with
+/// [`ErrorKind::WrongPhase`](crate::parser::ErrorKind::WrongPhase).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Phase {
+    /// Units hold, move, support, and convoy. Orders are
+    /// [`MappedMainOrder`](crate::judge::MappedMainOrder)s, adjudicated by a
+    /// [`Submission`](crate::judge::Submission).
     Main,
+    /// Dislodged units retreat or disband. Orders are
+    /// [`MappedRetreatOrder`](crate::judge::MappedRetreatOrder)s, adjudicated by a
+    /// [`retreat::Context`](crate::judge::retreat::Context).
     Retreat,
+    /// Nations build or disband units. Orders are
+    /// [`MappedBuildOrder`](crate::judge::MappedBuildOrder)s, adjudicated by a
+    /// [`build::Context`](crate::judge::build::Context).
     Build,
 }
 
+impl Phase {
+    /// Whether units move, support, and convoy in this phase.
+    pub fn is_main(self) -> bool {
+        self == Phase::Main
+    }
+
+    /// Whether dislodged units retreat or disband in this phase.
+    pub fn is_retreat(self) -> bool {
+        self == Phase::Retreat
+    }
+
+    /// Whether nations build or disband units to match their supply center count in this phase.
+    pub fn is_build(self) -> bool {
+        self == Phase::Build
+    }
+}
+
 impl ShortName for Phase {
-    fn short_name(&self) -> std::borrow::Cow<'_, str> {
+    fn short_name(&self) -> alloc::borrow::Cow<'_, str> {
         match self {
             Phase::Main => Cow::Borrowed("M"),
             Phase::Retreat => Cow::Borrowed("R"),
@@ -44,8 +75,26 @@ pub enum Season {
     Winter,
 }
 
+impl Season {
+    /// Whether this is the spring season, which begins each year.
+    pub fn is_spring(self) -> bool {
+        self == Season::Spring
+    }
+
+    /// Whether this is the fall season. Supply centers change hands at the end of fall,
+    /// after any retreats.
+    pub fn is_fall(self) -> bool {
+        self == Season::Fall
+    }
+
+    /// Whether this is the winter season, in which builds and disbands normally occur.
+    pub fn is_winter(self) -> bool {
+        self == Season::Winter
+    }
+}
+
 impl ShortName for Season {
-    fn short_name(&self) -> std::borrow::Cow<'_, str> {
+    fn short_name(&self) -> alloc::borrow::Cow<'_, str> {
         match self {
             Season::Spring => Cow::Borrowed("S"),
             Season::Summer => Cow::Borrowed("U"),
@@ -70,6 +119,19 @@ impl FromStr for Season {
 }
 
 /// Represents a specific point in game time.
+///
+/// # Game Flow
+/// A year in the standard game proceeds as follows:
+///
+/// 1. Spring main phase
+/// 1. Spring retreat phase, if any unit was dislodged in the spring main phase
+/// 1. Fall main phase
+/// 1. Fall retreat phase, if any unit was dislodged in the fall main phase
+/// 1. Supply centers are captured by the units occupying them
+/// 1. Winter build phase, if any nation has more or fewer units than supply centers
+///
+/// Use [`Time::requires_retreat_phase`] and [`Time::requires_build_phase`] to determine
+/// whether the optional phases should be played or skipped.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Time(usize, Season, Phase);
 
@@ -92,10 +154,27 @@ impl Time {
     pub fn phase(&self) -> Phase {
         self.2
     }
+
+    /// Whether a retreat phase should follow this turn, given the number of units dislodged
+    /// during it. Only main phases can be followed by retreats.
+    pub fn requires_retreat_phase(&self, dislodgements: usize) -> bool {
+        self.phase().is_main() && dislodgements > 0
+    }
+
+    /// Whether a build phase should follow this fall turn, given each nation's adjustment:
+    /// the number of supply centers it owns after capture minus the number of units it has.
+    ///
+    /// A fall main phase followed by a retreat phase should not be considered complete until
+    /// the retreat phase is resolved, since retreats and disbands change the unit counts.
+    pub fn requires_build_phase(&self, deltas: impl IntoIterator<Item = i16>) -> bool {
+        self.season().is_fall()
+            && !self.phase().is_build()
+            && deltas.into_iter().any(|delta| delta != 0)
+    }
 }
 
 impl ShortName for Time {
-    fn short_name(&self) -> std::borrow::Cow<'_, str> {
+    fn short_name(&self) -> alloc::borrow::Cow<'_, str> {
         Cow::Owned(format!(
             "{}{}{}",
             self.season().short_name(),
@@ -144,7 +223,7 @@ impl Serialize for Time {
 #[cfg(feature = "serde")]
 impl<'de> Deserialize<'de> for Time {
     fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
-        use std::fmt;
+        use core::fmt;
 
         struct TimeVisitor;
 
@@ -171,7 +250,7 @@ impl<'de> Deserialize<'de> for Time {
 #[cfg(test)]
 mod test {
     use super::*;
-    use std::str::FromStr;
+    use core::str::FromStr;
 
     #[test]
     fn parse_time() {
@@ -181,6 +260,24 @@ mod test {
         );
     }
 
+    #[test]
+    fn requires_retreat_phase() {
+        let spring = Time::new(Season::Spring, 1901, Phase::Main);
+        assert!(spring.requires_retreat_phase(1));
+        assert!(!spring.requires_retreat_phase(0));
+        assert!(!Time::new(Season::Spring, 1901, Phase::Retreat).requires_retreat_phase(1));
+    }
+
+    #[test]
+    fn requires_build_phase() {
+        let fall = Time::new(Season::Fall, 1901, Phase::Retreat);
+        assert!(fall.requires_build_phase([0, 1, -1]));
+        assert!(!fall.requires_build_phase([0, 0]));
+        assert!(Time::new(Season::Fall, 1901, Phase::Main).requires_build_phase([2]));
+        assert!(!Time::new(Season::Spring, 1901, Phase::Main).requires_build_phase([2]));
+        assert!(!Time::new(Season::Fall, 1901, Phase::Build).requires_build_phase([2]));
+    }
+
     #[test]
     fn cmp() {
         let turns = ["S1901M", "S1901R", "F1901M", "F1901R", "F1901B", "W1901B"];
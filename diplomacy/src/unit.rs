@@ -1,9 +1,16 @@
 use crate::parser::{Error, ErrorKind};
-use crate::{geo::Location, geo::RegionKey, Command, Nation, Order, ShortName};
-use std::borrow::{Borrow, Cow};
+use crate::{
+    geo::Location, geo::ProvinceKey, geo::RegionKey, geo::Terrain, Command, Nation, Order,
+    ShortName,
+};
+use alloc::borrow::{Borrow, Cow};
+use alloc::vec::Vec;
+use core::hash::{BuildHasher, Hash};
+use core::str::FromStr;
+#[cfg(not(feature = "std"))]
+use hashbrown::HashMap;
+#[cfg(feature = "std")]
 use std::collections::HashMap;
-use std::hash::{BuildHasher, Hash};
-use std::str::FromStr;
 
 /// The type of a military unit. Armies are convoyable land-based units; fleets
 /// are sea-going units which are able to convoy armies.
@@ -19,6 +26,32 @@ pub enum UnitType {
     Fleet,
 }
 
+impl UnitType {
+    /// Every unit type, in the order they are usually listed.
+    pub fn all() -> [UnitType; 2] {
+        [UnitType::Army, UnitType::Fleet]
+    }
+
+    /// The full name of the unit type, such as "Army". This parses back into the same unit type,
+    /// as does [`ShortName::short_name`].
+    pub fn name(self) -> &'static str {
+        match self {
+            UnitType::Army => "Army",
+            UnitType::Fleet => "Fleet",
+        }
+    }
+
+    /// Whether a unit of this type can occupy a region of the given terrain, or cross a
+    /// border of the given terrain.
+    pub(crate) fn can_occupy(self, terrain: Terrain) -> bool {
+        match terrain {
+            Terrain::Coast => true,
+            Terrain::Land => self == UnitType::Army,
+            Terrain::Sea => self == UnitType::Fleet,
+        }
+    }
+}
+
 impl FromStr for UnitType {
     type Err = Error;
 
@@ -32,7 +65,7 @@ impl FromStr for UnitType {
 }
 
 impl ShortName for UnitType {
-    fn short_name(&self) -> std::borrow::Cow<'_, str> {
+    fn short_name(&self) -> alloc::borrow::Cow<'_, str> {
         Cow::Borrowed(match *self {
             UnitType::Army => "A",
             UnitType::Fleet => "F",
@@ -66,6 +99,14 @@ impl<'a> Unit<'a> {
     pub fn unit_type(&self) -> UnitType {
         self.unit_type
     }
+
+    /// Take ownership of the unit's nation, so that the unit no longer borrows from anything.
+    pub fn into_owned(self) -> Unit<'static> {
+        Unit {
+            nation: Cow::Owned(self.nation.into_owned()),
+            unit_type: self.unit_type,
+        }
+    }
 }
 
 /// A unit's instantaneous position in a region.
@@ -94,6 +135,47 @@ impl<'a, L> UnitPosition<'a, L> {
             region: &self.region,
         }
     }
+
+    /// Create a view of the unit position that borrows both its nation and its region from
+    /// `self`. Unlike [`UnitPosition::as_region_ref`], this never allocates.
+    pub fn as_ref(&self) -> UnitPosition<'_, &L> {
+        UnitPosition {
+            unit: Unit::new(Cow::Borrowed(self.nation()), self.unit.unit_type()),
+            region: &self.region,
+        }
+    }
+
+    /// Take ownership of the unit's nation, keeping the region as-is.
+    pub fn with_owned(self) -> UnitPosition<'static, L> {
+        UnitPosition {
+            unit: self.unit.into_owned(),
+            region: self.region,
+        }
+    }
+}
+
+impl<L: Clone> UnitPosition<'_, &L> {
+    /// Clone the nation and region into a position that does not borrow from anything.
+    ///
+    /// This is the inverse of [`UnitPosition::as_ref`], and is how positions read from an
+    /// outcome or derived from orders are stored beyond the lifetime of a turn:
+    ///
+    /// ```
+    /// use diplomacy::geo::RegionKey;
+    /// use diplomacy::judge::MappedMainOrder;
+    /// use diplomacy::UnitPosition;
+    ///
+    /// let order = "FRA: A par -> bur".parse::<MappedMainOrder>().unwrap();
+    /// let position: UnitPosition<'static, RegionKey> = UnitPosition::from(&order).to_owned();
+    /// drop(order);
+    /// assert_eq!(position, "FRA: A par".parse().unwrap());
+    /// ```
+    pub fn to_owned(&self) -> UnitPosition<'static, L> {
+        UnitPosition {
+            unit: self.unit.clone().into_owned(),
+            region: self.region.clone(),
+        }
+    }
 }
 
 impl<'a> FromStr for UnitPosition<'a, RegionKey> {
@@ -161,6 +243,61 @@ impl<'a, L: Location> UnitPositions<L> for Vec<UnitPosition<'a, L>> {
     }
 }
 
+/// A list of units on the board, such as the units at the start of a turn.
+///
+/// This is the simplest way to give a [`Submission`](crate::judge::Submission) the
+/// positions of units which may not have received orders.
+///
+/// ```
+/// use diplomacy::geo::standard_map;
+/// use diplomacy::judge::{MappedMainOrder, OrderState, Rulebook, Submission};
+/// use diplomacy::StartingPositions;
+///
+/// let start = ["FRA: A par", "GER: A mun"]
+///     .iter()
+///     .map(|unit| unit.parse().unwrap())
+///     .collect::<StartingPositions>();
+///
+/// let order = "FRA: A par -> bur".parse::<MappedMainOrder>().unwrap();
+/// let submission = Submission::new(standard_map(), &start, vec![order.clone()]);
+/// let outcome = submission.adjudicate(Rulebook::default());
+///
+/// assert_eq!(OrderState::from(outcome.get(&order).unwrap()), OrderState::Succeeds);
+/// // Germany's army was not ordered, so it holds.
+/// assert_eq!(outcome.all_orders().count(), 2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StartingPositions(pub Vec<UnitPosition<'static, RegionKey>>);
+
+impl From<Vec<UnitPosition<'static, RegionKey>>> for StartingPositions {
+    fn from(positions: Vec<UnitPosition<'static, RegionKey>>) -> Self {
+        Self(positions)
+    }
+}
+
+impl FromIterator<UnitPosition<'static, RegionKey>> for StartingPositions {
+    fn from_iter<I: IntoIterator<Item = UnitPosition<'static, RegionKey>>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl UnitPositions<RegionKey> for StartingPositions {
+    fn unit_positions(&self) -> Vec<UnitPosition<'_, &RegionKey>> {
+        self.0.unit_positions()
+    }
+
+    fn find_province_occupier(
+        &self,
+        province: &ProvinceKey,
+    ) -> Option<UnitPosition<'_, &RegionKey>> {
+        self.0.find_province_occupier(province)
+    }
+
+    fn find_region_occupier(&self, region: &RegionKey) -> Option<Unit<'_>> {
+        self.0.find_region_occupier(region)
+    }
+}
+
 /// Infer unit positions from a collection of orders. This assumes orders are trustworthy
 /// and complete:
 ///
@@ -239,7 +376,7 @@ where
 #[cfg(test)]
 mod test {
     use super::{UnitPosition, UnitType};
-    use crate::{geo::RegionKey, Nation};
+    use crate::{geo::RegionKey, Nation, ShortName};
 
     #[test]
     fn parse_unit_type() {
@@ -251,9 +388,26 @@ mod test {
         assert_eq!(Ok(UnitType::Fleet), "f".parse());
     }
 
+    #[test]
+    fn roundtrip_unit_type_names() {
+        for unit_type in UnitType::all() {
+            assert_eq!(Ok(unit_type), unit_type.short_name().parse());
+            assert_eq!(Ok(unit_type), unit_type.name().parse());
+        }
+    }
+
     #[test]
     fn parse_unit_position() {
         let pos: UnitPosition<'_, RegionKey> = "FRA: F bre".parse().unwrap();
         assert_eq!(pos.nation(), &Nation::from("FRA"));
     }
+
+    #[test]
+    fn unit_position_owned_roundtrip() {
+        let pos: UnitPosition<'_, RegionKey> = "FRA: F bre".parse().unwrap();
+        let borrowed = pos.as_ref();
+        assert_eq!(borrowed.region, &pos.region);
+        assert_eq!(borrowed.to_owned(), pos);
+        assert_eq!(pos.clone().with_owned(), pos);
+    }
 }
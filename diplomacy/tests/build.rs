@@ -0,0 +1,324 @@
+#![cfg(test)]
+
+#[path = "./util.rs"]
+mod util;
+
+#[path = "./world.rs"]
+mod world;
+
+use std::cmp::Ordering;
+use std::iter::once;
+
+use diplomacy::geo::{self, RegionKey};
+use diplomacy::judge::build::{
+    Adjudicate, BuildPolicy, Context, DisbandCandidate, OrderOutcome, WorldState,
+};
+use diplomacy::judge::{retreat, Rulebook, Submission};
+use diplomacy::{Nation, UnitPosition, UnitType};
+use util::*;
+use world::TestWorld;
+
+/// Rules where the player has chosen which unit to remove ahead of time, falling back
+/// to the standard rules for everything else.
+struct PlayerChoice(RegionKey);
+
+impl Adjudicate for PlayerChoice {
+    fn disband_priority(&self, a: &DisbandCandidate<'_>, b: &DisbandCandidate<'_>) -> Ordering {
+        (b.region == self.0)
+            .cmp(&(a.region == self.0))
+            .then_with(|| Rulebook::default().disband_priority(a, b))
+    }
+}
+
+/// Same setup as DATC 6.J.4, but the player has opted to keep Livonia.
+#[test]
+fn civil_disorder_custom_disband_priority() {
+    let world = TestWorld::empty()
+        .with_occupier("stp", "ENG")
+        .with_occupier("war", "GER")
+        .with_occupier("sev", "TUR")
+        .with_unit("RUS: A lvn")
+        .with_unit("RUS: A ukr");
+
+    let last_time = initial_ownerships();
+    let context = Context::new(geo::standard_map(), &last_time, &world, vec![])
+        .with_rules(PlayerChoice(reg("ukr")));
+    let outcome = context.resolve();
+
+    assert_eq!(
+        outcome.civil_disorder,
+        once((UnitType::Army, reg("ukr"))).collect()
+    );
+}
+
+/// Resolve a single build order for Germany after it has captured Warsaw.
+fn build_in_captured_warsaw(rules: Rulebook, order: &str) -> OrderOutcome {
+    let world = TestWorld::empty().with_unit("GER: A sil");
+    let mut last_time = initial_ownerships();
+    last_time.insert(prov("war"), "GER".into());
+    let order = build_ord(order);
+    let context = Context::new(geo::standard_map(), &last_time, &world, vec![order.clone()])
+        .with_rules(rules);
+    let outcome = context.resolve();
+    *outcome.get(&order).expect("Order should be in results")
+}
+
+#[test]
+fn build_in_captured_sc_rejected_by_default() {
+    assert_eq!(
+        build_in_captured_warsaw(Rulebook::default(), "GER: A war build"),
+        OrderOutcome::InvalidProvince
+    );
+}
+
+#[test]
+fn build_in_captured_sc_with_any_owned_sc_policy() {
+    let mut rules = Rulebook::default();
+    rules.build_policy = BuildPolicy::AnyOwnedSc;
+
+    assert_eq!(
+        build_in_captured_warsaw(rules.clone(), "GER: A war build"),
+        OrderOutcome::Succeeds
+    );
+
+    // Owned home centers remain valid build sites.
+    assert_eq!(
+        build_in_captured_warsaw(rules.clone(), "GER: A ber build"),
+        OrderOutcome::Succeeds
+    );
+
+    // Non-supply-center provinces are still rejected.
+    assert_eq!(
+        build_in_captured_warsaw(rules.clone(), "GER: A pru build"),
+        OrderOutcome::InvalidProvince
+    );
+
+    // Supply centers controlled by another power are still rejected.
+    assert_eq!(
+        build_in_captured_warsaw(rules.clone(), "GER: A mos build"),
+        OrderOutcome::ForeignControlled
+    );
+
+    // Terrain must still be compatible with the unit.
+    assert_eq!(
+        build_in_captured_warsaw(rules, "GER: F war build"),
+        OrderOutcome::InvalidTerrain
+    );
+}
+
+/// Russia owes two disbands but only orders one; the ordered disband is honored and the
+/// shortfall is chosen by civil disorder, starting with the unit furthest from Moscow.
+#[test]
+fn partial_disband_shortfall_goes_to_civil_disorder() {
+    let world = TestWorld::empty()
+        .with_occupier("stp", "ENG")
+        .with_occupier("war", "GER")
+        .with_occupier("sev", "TUR")
+        .with_unit("RUS: A mos")
+        .with_unit("RUS: A ukr")
+        .with_unit("RUS: A pru");
+
+    let last_time = initial_ownerships();
+    let order = build_ord("RUS: A ukr disband");
+    let context = Context::new(geo::standard_map(), &last_time, &world, vec![order.clone()]);
+    let outcome = context.resolve();
+
+    assert_eq!(outcome.get(&order), Some(&OrderOutcome::Succeeds));
+    assert_eq!(
+        outcome.civil_disorder,
+        once((UnitType::Army, reg("pru"))).collect()
+    );
+    assert_eq!(
+        outcome.final_units[&Nation::from("RUS")],
+        once((UnitType::Army, reg("mos"))).collect()
+    );
+}
+
+/// Sorted accessors give the same order no matter how the underlying sets iterate.
+#[test]
+fn sorted_build_outputs_are_stable() {
+    fn resolve() -> (
+        Vec<(UnitType, RegionKey)>,
+        Vec<UnitPosition<'static, RegionKey>>,
+    ) {
+        let world = TestWorld::empty()
+            .with_occupier("stp", "ENG")
+            .with_occupier("war", "GER")
+            .with_occupier("sev", "TUR")
+            .with_unit("RUS: A mos")
+            .with_unit("RUS: A ukr")
+            .with_unit("RUS: A pru")
+            .with_unit("RUS: F bot")
+            .with_unit("GER: A sil")
+            .with_unit("TUR: F bla");
+
+        let last_time = initial_ownerships();
+        let context = Context::new(geo::standard_map(), &last_time, &world, vec![]);
+        let outcome = context.resolve();
+        (
+            outcome.civil_disorder_sorted(),
+            outcome.to_final_unit_positions_sorted(),
+        )
+    }
+
+    let (civil_disorder, final_units) = resolve();
+    assert_eq!(
+        civil_disorder,
+        vec![
+            (UnitType::Fleet, reg("bot")),
+            (UnitType::Army, reg("pru")),
+            (UnitType::Army, reg("ukr")),
+        ]
+    );
+    assert_eq!(
+        final_units,
+        vec![
+            unit_pos("TUR: F bla"),
+            unit_pos("RUS: A mos"),
+            unit_pos("GER: A sil"),
+        ]
+    );
+
+    for _ in 0..10 {
+        assert_eq!(resolve(), (civil_disorder.clone(), final_units.clone()));
+    }
+}
+
+/// Russia has lost every supply center, so all its units are disbanded and it may not build.
+#[test]
+fn eliminated_nation_disbands_all_units() {
+    let world = TestWorld::empty()
+        .with_occupier("stp", "ENG")
+        .with_occupier("mos", "TUR")
+        .with_occupier("war", "GER")
+        .with_occupier("sev", "TUR")
+        .with_unit("RUS: A ukr")
+        .with_unit("RUS: F bla");
+
+    let last_time = initial_ownerships();
+    let build = build_ord("RUS: A mos build");
+    let context = Context::new(geo::standard_map(), &last_time, &world, vec![build.clone()]);
+    let outcome = context.resolve();
+
+    assert_eq!(
+        outcome.get(&build),
+        Some(&OrderOutcome::RedeploymentProhibited)
+    );
+    assert_eq!(
+        outcome.civil_disorder_sorted(),
+        vec![(UnitType::Fleet, reg("bla")), (UnitType::Army, reg("ukr"))]
+    );
+    assert!(outcome
+        .to_final_unit_positions_sorted()
+        .iter()
+        .all(|pos| *pos.nation() != Nation::from("RUS")));
+}
+
+/// Germany has captured Warsaw from Russia, so Germany builds while Russia disbands.
+#[test]
+fn tallies_count_successful_orders() {
+    let world = TestWorld::empty()
+        .with_unit("GER: A sil")
+        .with_unit("RUS: A mos")
+        .with_unit("RUS: A ukr")
+        .with_unit("RUS: A pru")
+        .with_unit("RUS: A lvn");
+    let mut last_time = initial_ownerships();
+    last_time.insert(prov("war"), "GER".into());
+
+    let orders = vec![
+        build_ord("GER: A ber build"),
+        build_ord("GER: F kie build"),
+        build_ord("GER: A war build"),
+        build_ord("RUS: A pru disband"),
+    ];
+    let context = Context::new(geo::standard_map(), &last_time, &world, orders);
+    let outcome = context.resolve();
+
+    let tallies = outcome.tallies();
+    assert_eq!(tallies.len(), 2);
+    assert_eq!(tallies[&Nation::from("GER")], (2, 0));
+    assert_eq!(tallies[&Nation::from("RUS")], (0, 1));
+}
+
+/// The outcome of one build phase is the world state of the next when no units move between
+/// them.
+#[test]
+fn consecutive_build_phases_chain() {
+    let world = TestWorld::empty().with_unit("RUS: A mos");
+    let last_time = initial_ownerships();
+    let first_orders = vec![build_ord("RUS: A war build"), build_ord("RUS: F sev build")];
+    let first = Context::new(geo::standard_map(), &last_time, &world, first_orders);
+    let first_outcome = first.resolve();
+    assert_eq!(first_outcome.unit_count(&Nation::from("RUS")), 3);
+
+    let ownerships = first.to_ownerships();
+    let second_orders = vec![build_ord("RUS: A war build"), build_ord("RUS: A stp build")];
+    let second = Context::new(
+        geo::standard_map(),
+        &ownerships,
+        &first_outcome,
+        second_orders.clone(),
+    );
+    let second_outcome = second.resolve();
+
+    assert_eq!(
+        second_outcome.get(&second_orders[0]),
+        Some(&OrderOutcome::OccupiedProvince)
+    );
+    assert_eq!(
+        second_outcome.get(&second_orders[1]),
+        Some(&OrderOutcome::Succeeds)
+    );
+    assert_eq!(
+        second_outcome.to_final_unit_positions_sorted(),
+        vec![
+            unit_pos("RUS: A mos"),
+            unit_pos("RUS: F sev"),
+            unit_pos("RUS: A stp"),
+            unit_pos("RUS: A war"),
+        ]
+    );
+}
+
+#[test]
+fn build_phase_from_retreat_outcome() {
+    let submission = Submission::with_inferred_state(
+        geo::standard_map(),
+        vec![
+            ord("FRA: A bur -> mun"),
+            ord("FRA: A ruh Supports A bur -> mun"),
+            ord("GER: A mun Hold"),
+        ],
+    );
+    let main_outcome = submission.adjudicate(Rulebook::default());
+    let start = main_outcome.to_retreat_start();
+    let retreat_context = retreat::Context::new(&start, vec![retreat_ord("GER: A mun -> kie")]);
+    let retreat_outcome = retreat_context.resolve();
+
+    // France took Munich; Germany keeps its unoccupied home centers.
+    let last_time = initial_ownerships();
+    let orders = vec![
+        build_ord("FRA: A par build"),
+        build_ord("GER: A ber build"),
+        build_ord("GER: A kie build"),
+    ];
+    let context = Context::from_previous(
+        geo::standard_map(),
+        &last_time,
+        &retreat_outcome,
+        orders.clone(),
+    );
+    let outcome = context.resolve();
+
+    assert_eq!(outcome.get(&orders[0]), Some(&OrderOutcome::Succeeds));
+    assert_eq!(outcome.get(&orders[1]), Some(&OrderOutcome::Succeeds));
+    assert_eq!(
+        outcome.get(&orders[2]),
+        Some(&OrderOutcome::OccupiedProvince)
+    );
+    assert_eq!(
+        context.current_owner(&prov("mun")),
+        Some(&Nation::from("FRA"))
+    );
+}
@@ -11,7 +11,10 @@ use std::iter::once;
 use diplomacy::{
     geo,
     judge::{
-        self, AttackOutcome, IllegalOrder, OrderOutcome,
+        self,
+        build::{self, DisbandDistance},
+        support::SupportCoast,
+        AttackOutcome, IllegalOrder, OrderOutcome,
         OrderState::{Fails, Succeeds},
         Rulebook, Submission,
     },
@@ -65,7 +68,7 @@ fn t6a06_ordering_a_unit_of_another_country() {
         &vec![unit_pos("ENG: F lon")],
         vec![order.clone()],
     );
-    let outcome = submission.adjudicate(Rulebook);
+    let outcome = submission.adjudicate(Rulebook::default());
     assert_eq!(
         outcome.get(&order).unwrap(),
         &OrderOutcome::Illegal(IllegalOrder::ForeignUnit)
@@ -198,9 +201,9 @@ fn t6b07_supporting_with_unspecified_coast() {
     };
 }
 
-/// This implementation of the adjudicator deems correction of orders such
+/// By default, this implementation of the adjudicator deems correction of orders such
 /// as this one to be the responsibility of the caller, and will execute received
-/// orders with region-level precision.
+/// orders with region-level precision. See the next test for the opt-in correction.
 ///
 /// https://webdiplomacy.net/doc/DATC_v3_0.html#6.B.8
 #[test]
@@ -214,6 +217,39 @@ fn t6b08_supporting_with_unspecified_coast_when_only_one_coast_is_possible() {
     };
 }
 
+/// With [`SupportCoast::InferUnambiguous`], the support is applied to the only coast of
+/// Spain the fleet in Gascony can reach.
+///
+/// https://webdiplomacy.net/doc/DATC_v3_0.html#6.B.8
+#[test]
+fn t6b08_supporting_with_unspecified_coast_inferred() {
+    let orders = [
+        "FRA: F por Supports F gas -> spa",
+        "FRA: F gas -> spa(nc)",
+        "ITA: F lyo Supports F wes -> spa(sc)",
+        "ITA: F wes -> spa(sc)",
+    ]
+    .into_iter()
+    .map(ord)
+    .collect::<Vec<_>>();
+
+    let mut rules = Rulebook::default();
+    rules.support_coast = SupportCoast::InferUnambiguous;
+
+    let submission = Submission::with_inferred_state(geo::standard_map(), orders.clone());
+    let outcome = submission.adjudicate(rules);
+
+    assert_eq!(
+        judge::OrderState::from(outcome.get(&orders[1]).unwrap()),
+        Fails
+    );
+    assert_eq!(
+        judge::OrderState::from(outcome.get(&orders[3]).unwrap()),
+        Fails
+    );
+    assert_eq!(outcome.supports_for(&orders[1]), vec![&orders[0]]);
+}
+
 /// https://webdiplomacy.net/doc/DATC_v3_0.html#6.B.9
 #[test]
 fn t6b09_supporting_with_wrong_coast() {
@@ -239,7 +275,7 @@ fn t6b10_unit_ordered_with_wrong_coast() {
         &vec![unit_pos("FRA: F spa(sc)")],
         vec![order.clone()],
     );
-    let outcome = submission.adjudicate(Rulebook);
+    let outcome = submission.adjudicate(Rulebook::default());
     dbg!(outcome.get(&order));
     assert_eq!(
         outcome.get(&order).expect("Order should have outcome"),
@@ -256,7 +292,7 @@ fn t6b11_coast_can_not_be_ordered_to_change() {
         &vec![unit_pos("FRA: F spa(nc)")],
         vec![order.clone()],
     );
-    let outcome = submission.adjudicate(Rulebook);
+    let outcome = submission.adjudicate(Rulebook::default());
     dbg!(outcome.get(&order));
     assert_eq!(
         outcome.get(&order).expect("Order should have outcome"),
@@ -2217,3 +2253,30 @@ fn t6j11_distance_to_owned_supply_center() {
 
     assert_eq!(civil_disorder, once((UnitType::Army, reg("tus"))).collect());
 }
+
+/// https://webdiplomacy.net/doc/DATC_v3_0.html#6.J.11
+///
+/// Under the 1971 and 1982 rules, distance is measured to home supply centers instead, so
+/// the army in Tuscany is kept because it borders Rome.
+#[test]
+fn t6j11_distance_to_home_supply_center() {
+    let world = TestWorld::empty()
+        .with_occupier("ven", "AUS")
+        .with_occupier("rom", "FRA")
+        .with_occupier("nap", "AUS")
+        .with_unit("ITA: A war")
+        .with_unit("ITA: A tus");
+
+    let mut rules = Rulebook::default();
+    rules.disband_distance = DisbandDistance::HomeSc;
+
+    let last_time = initial_ownerships();
+    let context =
+        build::Context::new(geo::standard_map(), &last_time, &world, vec![]).with_rules(rules);
+    let outcome = context.resolve();
+
+    assert_eq!(
+        outcome.civil_disorder,
+        once((UnitType::Army, reg("war"))).collect()
+    );
+}
@@ -5,11 +5,19 @@ extern crate diplomacy;
 mod util;
 mod world;
 
-use diplomacy::geo;
-use diplomacy::judge::{OrderState, Rulebook, Submission};
-use diplomacy::order::{ConvoyedMove, MainCommand, MoveCommand, Order, SupportedOrder};
+use diplomacy::geo::builder::AdjacencyErrorKind;
+use diplomacy::geo::{self, ProvinceKey, RegionKey, Terrain};
+use diplomacy::judge::{
+    build, is_stalemate_line, is_static, retreat, Adjudicate, Context, ConvoyOutcome, IllegalOrder,
+    MappedMainOrder, MappedRetreatOrder, MoveRoute, OrderOutcome, OrderState, PhaseOutcome,
+    ResolverState, Rulebook, Submission, Tracing, TurnSummary,
+};
+use diplomacy::order::{
+    ConvoyIntent, ConvoyedMove, InferUnitTypeError, MainCommand, MoveCommand, Order, SupportedOrder,
+};
+use diplomacy::parser::ErrorKind;
 
-use diplomacy::{Nation, UnitType};
+use diplomacy::{Nation, ShortName, UnitPosition, UnitType};
 
 use crate::util::*;
 
@@ -53,7 +61,7 @@ fn dipmath_figure9() {
         ],
     );
 
-    let result = orders.adjudicate(Rulebook);
+    let result = orders.adjudicate(Rulebook::default());
 
     for order in orders.submitted_orders() {
         assert_eq!(OrderState::Succeeds, result.get(order).unwrap().into());
@@ -106,7 +114,7 @@ fn dipmath_figure6() {
         .find_border_between(&reg("sil"), &reg("boh"))
         .is_some());
 
-    let result = orders.adjudicate(Rulebook);
+    let result = orders.adjudicate(Rulebook::default());
     for o in orders.submitted_orders() {
         assert_eq!(
             if o.nation == ger {
@@ -163,7 +171,7 @@ fn dipmath_figure16() {
         ],
     );
 
-    let result = orders.adjudicate(Rulebook);
+    let result = orders.adjudicate(Rulebook::default());
     for o in orders.submitted_orders() {
         assert_eq!(
             if o.nation != ita {
@@ -175,3 +183,1647 @@ fn dipmath_figure16() {
         );
     }
 }
+
+/// Same setup as DATC 6.G.1, except England refuses the convoy it ordered. This turns the
+/// swap into a head-to-head battle, and both units bounce.
+#[test]
+fn via_land_refuses_same_country_convoy() {
+    judge! {
+        "ENG: A nwy -> swe via land": OrderState::Fails,
+        "ENG: F ska convoys nwy -> swe",
+        "RUS: A swe -> nwy": OrderState::Fails,
+    };
+
+    judge! {
+        "ENG: A nwy -> swe": OrderState::Succeeds,
+        "ENG: F ska convoys nwy -> swe",
+        "RUS: A swe -> nwy": OrderState::Succeeds,
+    };
+}
+
+#[test]
+fn submission_with_state_carries_ownerships() {
+    let orders = vec![ord("FRA: A par -> bur"), ord("GER: A mun Hold")];
+    let submission = Submission::with_state(
+        geo::standard_map(),
+        &orders,
+        initial_ownerships(),
+        orders.clone(),
+    );
+
+    let outcome = submission.adjudicate(Rulebook::default());
+    assert_eq!(
+        outcome
+            .ownerships()
+            .and_then(|owners| owners.get(&prov("par"))),
+        Some(&Nation::from("FRA"))
+    );
+    assert_eq!(
+        outcome.get(&orders[0]).map(OrderState::from),
+        Some(OrderState::Succeeds)
+    );
+    assert!(Submission::with_inferred_state(geo::standard_map(), orders)
+        .ownerships()
+        .is_none());
+}
+
+#[test]
+fn ownerships_after_fall_apply_captures() {
+    let orders = vec![
+        ord("FRA: A bur -> bel"),
+        ord("ITA: A ven -> tri"),
+        ord("ENG: F lon -> nth"),
+    ];
+    let submission = Submission::with_state(
+        geo::standard_map(),
+        &orders,
+        initial_ownerships(),
+        orders.clone(),
+    );
+    let outcome = submission.adjudicate(Rulebook::default());
+    let after = outcome.ownerships_after_fall().unwrap();
+
+    // France captures neutral Belgium and Italy captures Austrian Trieste. England keeps
+    // London even though its fleet left.
+    assert_eq!(after.get(&prov("bel")), Some(&Nation::from("FRA")));
+    assert_eq!(after.get(&prov("tri")), Some(&Nation::from("ITA")));
+    assert_eq!(after.get(&prov("ven")), Some(&Nation::from("ITA")));
+    assert_eq!(after.get(&prov("lon")), Some(&Nation::from("ENG")));
+    assert_eq!(after.get(&prov("nth")), None);
+    assert_eq!(after.len(), initial_ownerships().len() + 1);
+
+    assert!(Submission::with_inferred_state(geo::standard_map(), orders)
+        .adjudicate(Rulebook::default())
+        .ownerships_after_fall()
+        .is_none());
+}
+
+#[test]
+fn order_outcome_is_successful_matches_order_state() {
+    let submission = Submission::with_inferred_state(
+        geo::standard_map(),
+        [
+            "AUS: A bud Supports A tri -> vie",
+            "AUS: A tri -> vie",
+            "GER: A mun -> boh",
+            "GER: A sil -> boh",
+            "ITA: A vie Hold",
+            "ENG: A lon -> bel",
+            "ENG: F nth convoys lon -> bel",
+            "FRA: F bre Supports F eng",
+            "FRA: F eng Hold",
+        ]
+        .into_iter()
+        .map(ord)
+        .collect(),
+    );
+
+    let outcome = submission.adjudicate(Rulebook::default());
+    let mut saw_success = false;
+    let mut saw_failure = false;
+    for (_, order_outcome) in outcome.all_orders_with_outcomes() {
+        let state = OrderState::from(order_outcome);
+        assert_eq!(order_outcome.to_order_state(), state);
+        assert_eq!(order_outcome.is_successful(), bool::from(state));
+        saw_success |= order_outcome.is_successful();
+        saw_failure |= !order_outcome.is_successful();
+    }
+
+    assert!(saw_success && saw_failure);
+}
+
+#[test]
+fn order_infer_unit_type() {
+    let map = geo::standard_map();
+    let fra = Nation::from("FRA");
+
+    let army = Order::infer(map, fra.clone(), reg("par"), MainCommand::Hold).unwrap();
+    assert_eq!(army.unit_type, UnitType::Army);
+
+    let fleet = Order::infer(map, fra.clone(), reg("mao"), MainCommand::Hold).unwrap();
+    assert_eq!(fleet.unit_type, UnitType::Fleet);
+
+    let coast_fleet = Order::infer(map, fra.clone(), reg("spa(nc)"), MainCommand::Hold).unwrap();
+    assert_eq!(coast_fleet.unit_type, UnitType::Fleet);
+
+    assert_eq!(
+        Order::infer(map, fra.clone(), reg("bre"), MainCommand::Hold),
+        Err(InferUnitTypeError::Ambiguous)
+    );
+    assert_eq!(
+        Order::infer(map, fra, reg("xyz"), MainCommand::Hold),
+        Err(InferUnitTypeError::RegionNotFound)
+    );
+
+    let boxed: Result<_, Box<dyn std::error::Error>> = (|| {
+        Ok(Order::infer(
+            map,
+            Nation::from("FRA"),
+            reg("bre"),
+            MainCommand::Hold,
+        )?)
+    })();
+    assert_eq!(
+        boxed.unwrap_err().to_string(),
+        "region can be occupied by either an army or a fleet"
+    );
+}
+
+#[test]
+fn supports_report_kind_and_outcome() {
+    use diplomacy::judge::{SupportKind, SupportOutcome};
+
+    let submission = Submission::with_inferred_state(
+        geo::standard_map(),
+        [
+            "FRA: A bur Supports A par",
+            "FRA: A par Hold",
+            "FRA: A gas Supports A mar -> spa",
+            "FRA: A mar -> spa",
+            "GER: A mun -> bur",
+            "ITA: A tyr Supports A ven",
+            "ITA: A ven Hold",
+            "ITA: A rom Supports A nap -> apu",
+            "ITA: A nap -> apu",
+            "AUS: A tus -> rom",
+        ]
+        .into_iter()
+        .map(ord)
+        .collect(),
+    );
+
+    let outcome = submission.adjudicate(Rulebook::default());
+    let mut supports = outcome
+        .supports()
+        .map(|(order, kind, support_outcome)| {
+            (
+                order.region.to_string(),
+                kind,
+                support_outcome.is_successful(),
+            )
+        })
+        .collect::<Vec<_>>();
+    supports.sort_by(|a, b| a.0.cmp(&b.0));
+
+    assert_eq!(
+        supports,
+        vec![
+            ("bur".to_string(), SupportKind::Hold, false),
+            ("gas".to_string(), SupportKind::Move, true),
+            ("rom".to_string(), SupportKind::Move, false),
+            ("tyr".to_string(), SupportKind::Hold, true),
+        ]
+    );
+
+    let outcome_at = |region: &str| {
+        outcome
+            .supports()
+            .find(|(order, _, _)| order.region == reg(region))
+            .map(|(_, _, support_outcome)| support_outcome)
+            .unwrap()
+    };
+    assert_eq!(
+        outcome_at("bur"),
+        &SupportOutcome::CutBy(&ord("GER: A mun -> bur"))
+    );
+    assert_eq!(
+        outcome_at("rom"),
+        &SupportOutcome::CutBy(&ord("AUS: A tus -> rom"))
+    );
+    assert_eq!(outcome_at("tyr"), &SupportOutcome::NotDisrupted);
+    assert_eq!(outcome_at("gas"), &SupportOutcome::NotDisrupted);
+}
+
+#[test]
+fn illegal_order_policy_preserve() {
+    use diplomacy::judge::{IllegalOrder, IllegalOrderPolicy, OrderOutcome};
+
+    let orders = vec![ord("FRA: A par -> lon"), ord("GER: A bur -> par")];
+    let positions = vec![ord("FRA: A par Hold"), ord("GER: A bur Hold")];
+    let hold = ord("FRA: A par Hold");
+
+    let substituted = Submission::new(geo::standard_map(), &positions, orders.clone());
+    let outcome = substituted.adjudicate(Rulebook::default());
+    assert_eq!(outcome.all_orders().count(), 3);
+    assert!(outcome.get(&hold).is_some());
+
+    let preserved = Submission::new(geo::standard_map(), &positions, orders.clone())
+        .with_illegal_order_policy(IllegalOrderPolicy::Preserve);
+    let outcome = preserved.adjudicate(Rulebook::default());
+    assert_eq!(outcome.all_orders().count(), 2);
+    assert!(outcome.get(&hold).is_none());
+    assert_eq!(
+        outcome.get(&orders[0]),
+        Some(&OrderOutcome::Illegal(IllegalOrder::UnreachableDestination))
+    );
+
+    // The unit still holds, so it defends Paris.
+    assert_eq!(
+        outcome.get(&orders[1]).map(OrderState::from),
+        Some(OrderState::Fails)
+    );
+}
+
+/// Adjudicate orders with inferred state using `rules`, returning the state of `order`.
+fn order_state_with(rules: impl Adjudicate, orders: &[&str], order: &str) -> OrderState {
+    let submission = Submission::with_inferred_state(
+        geo::standard_map(),
+        orders.iter().copied().map(ord).collect(),
+    );
+    let outcome = submission.adjudicate(rules);
+    let order = ord(order);
+    outcome
+        .get(&order)
+        .expect("Order should be in outcome")
+        .into()
+}
+
+/// DATC 6.F.6
+const DISLODGED_CONVOY: &[&str] = &[
+    "ENG: F nth convoys lon -> hol",
+    "ENG: A lon -> hol",
+    "GER: A hol Supports A bel",
+    "GER: A bel Supports A hol",
+    "GER: F hel Supports F ska -> nth",
+    "GER: F ska -> nth",
+    "FRA: A pic -> bel",
+    "FRA: A bur Supports A pic -> bel",
+];
+
+#[test]
+fn disrupted_convoy_cut_policy() {
+    use diplomacy::judge::support::DisruptedConvoyCut;
+
+    assert_eq!(
+        order_state_with(Rulebook::default(), DISLODGED_CONVOY, "FRA: A pic -> bel"),
+        OrderState::Fails
+    );
+
+    let mut rules = Rulebook::default();
+    rules.disrupted_convoy_cut = DisruptedConvoyCut::Cuts;
+    assert_eq!(
+        order_state_with(rules.clone(), DISLODGED_CONVOY, "GER: A hol Supports A bel"),
+        OrderState::Fails
+    );
+    assert_eq!(
+        order_state_with(rules, DISLODGED_CONVOY, "FRA: A pic -> bel"),
+        OrderState::Succeeds
+    );
+}
+
+/// DATC 6.D.17
+const DISLODGED_SUPPORTER: &[&str] = &[
+    "RUS: F con Supports F bla -> ank",
+    "RUS: F bla -> ank",
+    "TUR: F ank -> con",
+    "TUR: A smy Supports F ank -> con",
+    "TUR: A arm -> ank",
+];
+
+#[test]
+fn dislodged_support_policy() {
+    use diplomacy::judge::support::DislodgedSupport;
+
+    assert_eq!(
+        order_state_with(
+            Rulebook::default(),
+            DISLODGED_SUPPORTER,
+            "RUS: F bla -> ank"
+        ),
+        OrderState::Fails
+    );
+
+    let mut rules = Rulebook::default();
+    rules.dislodged_support = DislodgedSupport::Sustained;
+    assert_eq!(
+        order_state_with(rules.clone(), DISLODGED_SUPPORTER, "RUS: F bla -> ank"),
+        OrderState::Succeeds
+    );
+    assert_eq!(
+        order_state_with(rules, DISLODGED_SUPPORTER, "TUR: F ank -> con"),
+        OrderState::Succeeds
+    );
+}
+
+/// DATC 6.D.10
+const SELF_DISLODGEMENT: &[&str] = &[
+    "GER: A ber Hold",
+    "GER: F kie -> ber",
+    "GER: A mun Supports F kie -> ber",
+];
+
+#[test]
+fn self_dislodgement_policy() {
+    use diplomacy::judge::SelfDislodgement;
+
+    assert_eq!(
+        order_state_with(Rulebook::default(), SELF_DISLODGEMENT, "GER: F kie -> ber"),
+        OrderState::Fails
+    );
+    assert_eq!(
+        order_state_with(Rulebook::default(), SELF_DISLODGEMENT, "GER: A ber Hold"),
+        OrderState::Succeeds
+    );
+
+    let mut rules = Rulebook::default();
+    rules.self_dislodgement = SelfDislodgement::Allowed;
+    assert_eq!(
+        order_state_with(rules.clone(), SELF_DISLODGEMENT, "GER: F kie -> ber"),
+        OrderState::Succeeds
+    );
+    assert_eq!(
+        order_state_with(rules.clone(), SELF_DISLODGEMENT, "GER: A ber Hold"),
+        OrderState::Fails
+    );
+
+    // Wrapping the rules keeps their policies.
+    assert_eq!(
+        order_state_with(Tracing::new(rules), SELF_DISLODGEMENT, "GER: F kie -> ber"),
+        OrderState::Succeeds
+    );
+}
+
+/// DATC 6.E.3
+const HELP_DISLODGING_OWN_UNIT: &[&str] = &[
+    "GER: A ber -> kie",
+    "GER: A mun Supports F kie -> ber",
+    "ENG: F kie -> ber",
+];
+
+#[test]
+fn help_dislodging_own_unit_policy() {
+    use diplomacy::judge::{AttackOutcome, HelpDislodgingOwnUnit, OrderOutcome};
+
+    let submission = Submission::with_inferred_state(
+        geo::standard_map(),
+        HELP_DISLODGING_OWN_UNIT.iter().copied().map(ord).collect(),
+    );
+    let ber = ord("GER: A ber -> kie");
+
+    let outcome = submission.adjudicate(Rulebook::default());
+    assert_eq!(
+        outcome.get(&ber),
+        Some(&OrderOutcome::Move(AttackOutcome::OccupierDefended))
+    );
+
+    let mut rules = Rulebook::default();
+    rules.help_dislodging_own_unit = HelpDislodgingOwnUnit::Allowed;
+    let outcome = submission.adjudicate(rules);
+    assert_eq!(
+        outcome.get(&ber),
+        Some(&OrderOutcome::Move(AttackOutcome::LostHeadToHead(&ord(
+            "ENG: F kie -> ber"
+        ))))
+    );
+}
+
+#[test]
+fn dislodger_of_hold_and_head_to_head() {
+    let attack = ord("FRA: A bur -> mun");
+
+    let submission = Submission::with_inferred_state(
+        geo::standard_map(),
+        vec![
+            attack.clone(),
+            ord("FRA: A ruh Supports A bur -> mun"),
+            ord("GER: A mun Hold"),
+        ],
+    );
+    let outcome = submission.adjudicate(Rulebook::default());
+    match outcome.get(&ord("GER: A mun Hold")) {
+        Some(OrderOutcome::Hold(hold)) => assert_eq!(hold.dislodger(), Some(&&attack)),
+        other => panic!("Expected a hold outcome, got {:?}", other),
+    }
+
+    let submission = Submission::with_inferred_state(
+        geo::standard_map(),
+        vec![
+            attack.clone(),
+            ord("FRA: A ruh Supports A bur -> mun"),
+            ord("GER: A mun -> bur"),
+        ],
+    );
+    let outcome = submission.adjudicate(Rulebook::default());
+    match outcome.get(&ord("GER: A mun -> bur")) {
+        Some(OrderOutcome::Move(attack_outcome)) => {
+            assert_eq!(attack_outcome.dislodger(), Some(&&attack))
+        }
+        other => panic!("Expected a move outcome, got {:?}", other),
+    }
+    match outcome.get(&attack) {
+        Some(OrderOutcome::Move(attack_outcome)) => assert_eq!(attack_outcome.dislodger(), None),
+        other => panic!("Expected a move outcome, got {:?}", other),
+    }
+}
+
+#[test]
+fn supports_for_excludes_friendly_fire() {
+    let submission = Submission::with_inferred_state(
+        geo::standard_map(),
+        [
+            "AUS: F tri Hold",
+            "AUS: A vie Supports A ven -> tri",
+            "ITA: A ven -> tri",
+            "ITA: A tyr Supports A ven -> tri",
+            "ITA: F adr Supports A ven -> tri",
+            "ITA: A rom Supports A ven",
+        ]
+        .into_iter()
+        .map(ord)
+        .collect(),
+    );
+
+    let outcome = submission.adjudicate(Rulebook::default());
+    assert_eq!(
+        outcome.supports_for(&ord("ITA: A ven -> tri")),
+        vec![
+            &ord("ITA: A tyr Supports A ven -> tri"),
+            &ord("ITA: F adr Supports A ven -> tri"),
+        ]
+    );
+    assert!(outcome.supports_for(&ord("AUS: F tri Hold")).is_empty());
+}
+
+/// DATC 6.F.16: the trace shows the resolver guessing both ways at the paradoxical convoy
+/// before settling on the moves' outcomes.
+#[test]
+fn tracing_records_pandins_paradox_guesses() {
+    let orders = [
+        "ENG: F lon Supports F wal -> eng",
+        "ENG: F wal -> eng",
+        "FRA: A bre -> lon",
+        "FRA: F eng convoys bre -> lon",
+        "GER: F nth Supports F bel -> eng",
+        "GER: F bel -> eng",
+    ]
+    .into_iter()
+    .map(ord)
+    .collect::<Vec<_>>();
+    let submission = Submission::with_inferred_state(geo::standard_map(), orders.clone());
+    let outcome = submission.adjudicate(Tracing::new(Rulebook::default()));
+    let entries = outcome.rules().entries();
+
+    let results = |order: &MappedMainOrder| {
+        entries
+            .iter()
+            .filter(|entry| entry.order == *order)
+            .map(|entry| entry.result)
+            .collect::<Vec<_>>()
+    };
+
+    for order in &orders {
+        assert!(
+            !results(order).is_empty(),
+            "{} was never adjudicated",
+            order
+        );
+    }
+
+    // The convoy and the support it threatens are each adjudicated under both guesses.
+    for order in [&orders[3], &orders[0]] {
+        let results = results(order);
+        assert!(results.contains(&OrderState::Succeeds), "{}", order);
+        assert!(results.contains(&OrderState::Fails), "{}", order);
+    }
+
+    // The last adjudication of each move is its final result.
+    for order in [&orders[1], &orders[2], &orders[5]] {
+        assert_eq!(
+            results(order).last().copied(),
+            outcome.get(order).map(OrderState::from),
+            "{}",
+            order
+        );
+    }
+}
+
+/// DATC 6.F.16, alongside an unrelated convoy that resolves normally.
+#[test]
+fn paradoxical_orders_reports_pandins_paradox() {
+    let submission = Submission::with_inferred_state(
+        geo::standard_map(),
+        [
+            "ENG: F lon Supports F wal -> eng",
+            "ENG: F wal -> eng",
+            "FRA: A bre -> lon",
+            "FRA: F eng convoys bre -> lon",
+            "GER: F nth Supports F bel -> eng",
+            "GER: F bel -> eng",
+            "TUR: A smy -> gre",
+            "TUR: F aeg convoys smy -> gre",
+        ]
+        .into_iter()
+        .map(ord)
+        .collect(),
+    );
+
+    let outcome = submission.adjudicate(Rulebook::default());
+    assert_eq!(
+        outcome.paradoxical_orders().collect::<Vec<_>>(),
+        vec![&ord("FRA: F eng convoys bre -> lon")]
+    );
+}
+
+#[cfg(feature = "resolution-stats")]
+#[test]
+fn resolution_stats_count_paradox_guesses() {
+    // Nothing depends on itself here, so every order is resolved once with one guess.
+    let simple = Submission::with_inferred_state(
+        geo::standard_map(),
+        vec![
+            ord("FRA: A bur -> mun"),
+            ord("FRA: A ruh Supports A bur -> mun"),
+            ord("GER: A mun Hold"),
+        ],
+    );
+    let outcome = simple.adjudicate(Rulebook::default());
+    let stats = outcome.resolution_stats();
+    assert_eq!(stats.guesses, 3);
+    assert!(stats.resolutions.values().all(|&count| count == 1));
+
+    // Pandin's paradox, from DATC 6.F.16, needs repeated guesses to find the paradox.
+    let paradox = Submission::with_inferred_state(
+        geo::standard_map(),
+        [
+            "ENG: F lon Supports F wal -> eng",
+            "ENG: F wal -> eng",
+            "FRA: A bre -> lon",
+            "FRA: F eng convoys bre -> lon",
+            "GER: F nth Supports F bel -> eng",
+            "GER: F bel -> eng",
+        ]
+        .into_iter()
+        .map(ord)
+        .collect(),
+    );
+    let outcome = paradox.adjudicate(Rulebook::default());
+    let stats = outcome.resolution_stats();
+    assert!(stats.guesses > 10, "{:?}", stats);
+    assert!(stats.resolutions.values().any(|&count| count > 1));
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn conformance_report_lists_failed_cases() {
+    use diplomacy::judge::{run_conformance, ConformanceCase};
+
+    let cases = vec![
+        ConformanceCase::new(
+            "6.A.1",
+            vec![ord("ENG: F nth -> pic")],
+            vec![(ord("ENG: F nth -> pic"), OrderState::Fails)],
+        ),
+        ConformanceCase::new(
+            "wrong",
+            vec![ord("FRA: A bur -> mun"), ord("GER: A mun Hold")],
+            vec![(ord("FRA: A bur -> mun"), OrderState::Succeeds)],
+        ),
+    ];
+
+    let report = run_conformance(geo::standard_map(), Rulebook::default(), cases);
+    assert!(!report.passed());
+    let failed = report.failed().collect::<Vec<_>>();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].name, "wrong");
+    assert_eq!(
+        failed[0].failures,
+        vec!["FRA: A bur -> mun: expected Succeeds, got Fails: occupier defended".to_string()]
+    );
+}
+
+#[test]
+fn order_outcome_display() {
+    let submission = Submission::with_inferred_state(
+        geo::standard_map(),
+        vec![
+            ord("FRA: A bur -> mun"),
+            ord("FRA: A ruh Supports A bur -> mun"),
+            ord("GER: A kie -> ruh"),
+            ord("GER: A sil -> mun"),
+            ord("ENG: F nth -> hol"),
+        ],
+    );
+    let outcome = submission.adjudicate(Rulebook::default());
+    let display = |order: &str| outcome.get(&ord(order)).unwrap().to_string();
+
+    assert_eq!(display("ENG: F nth -> hol"), "Succeeds");
+    assert_eq!(display("GER: A kie -> ruh"), "Fails: occupier defended");
+    assert_eq!(
+        display("FRA: A bur -> mun"),
+        "Prevented by GER: A sil -> mun"
+    );
+    assert_eq!(
+        display("FRA: A ruh Supports A bur -> mun"),
+        "Cut by GER: A kie -> ruh"
+    );
+
+    assert_eq!(
+        build::OrderOutcome::OccupiedProvince.to_string(),
+        "Fails: province occupied"
+    );
+    assert_eq!(
+        retreat::OrderOutcome::Prevented("GER: A mun -> sil").to_string(),
+        "Prevented by GER: A mun -> sil"
+    );
+    assert_eq!(
+        retreat::OrderOutcome::<&str>::InvalidDestination(retreat::DestStatus::Occupied)
+            .to_string(),
+        "Fails: destination occupied"
+    );
+}
+
+/// DATC 6.F.5: the convoying fleet is attacked from two sides with equal strength, so it
+/// survives and the convoy reports that the attacks did not disrupt it.
+#[test]
+fn beleaguered_convoy_survives_attack() {
+    let submission = Submission::with_inferred_state(
+        geo::standard_map(),
+        vec![
+            ord("ENG: F nth convoys lon -> hol"),
+            ord("ENG: A lon -> hol"),
+            ord("FRA: F eng -> nth"),
+            ord("FRA: F bel Supports F eng -> nth"),
+            ord("GER: F ska -> nth"),
+            ord("GER: F den Supports F ska -> nth"),
+            ord("RUS: F bar convoys stp -> nwy"),
+            ord("RUS: A stp -> nwy"),
+        ],
+    );
+    let outcome = submission.adjudicate(Rulebook::default());
+    assert_eq!(
+        outcome.get(&ord("ENG: F nth convoys lon -> hol")),
+        Some(&OrderOutcome::Convoy(ConvoyOutcome::SurvivedAttack))
+    );
+    assert_eq!(
+        outcome.get(&ord("RUS: F bar convoys stp -> nwy")),
+        Some(&OrderOutcome::Convoy(ConvoyOutcome::NotDisrupted))
+    );
+}
+
+/// A unit of the same nation moving into the convoying fleet's province can't dislodge it,
+/// so it isn't reported as an attack.
+#[test]
+fn friendly_move_into_convoying_fleet_is_not_an_attack() {
+    let submission = Submission::with_inferred_state(
+        geo::standard_map(),
+        vec![
+            ord("ENG: F nth convoys lon -> hol"),
+            ord("ENG: A lon -> hol"),
+            ord("ENG: F edi -> nth"),
+        ],
+    );
+    let outcome = submission.adjudicate(Rulebook::default());
+    assert_eq!(
+        outcome.get(&ord("ENG: F nth convoys lon -> hol")),
+        Some(&OrderOutcome::Convoy(ConvoyOutcome::NotDisrupted))
+    );
+}
+
+/// DATC 6.G.1 and 6.G.3: an army moving to an adjacent province uses an available convoy,
+/// but moves over land when that convoy is disrupted.
+#[test]
+fn move_route_reports_adjacent_convoys() {
+    let swap = Submission::with_inferred_state(
+        geo::standard_map(),
+        [
+            "ENG: A nwy -> swe",
+            "ENG: F ska convoys nwy -> swe",
+            "RUS: F swe -> nwy",
+        ]
+        .into_iter()
+        .map(ord)
+        .collect(),
+    );
+    let outcome = swap.adjudicate(Rulebook::default());
+    assert_eq!(
+        outcome.move_route(&ord("ENG: A nwy -> swe")),
+        Some(MoveRoute::Convoyed)
+    );
+    assert_eq!(
+        outcome.move_route(&ord("RUS: F swe -> nwy")),
+        Some(MoveRoute::Direct)
+    );
+    assert_eq!(
+        outcome.move_route(&ord("ENG: F ska convoys nwy -> swe")),
+        None
+    );
+
+    let disrupted = Submission::with_inferred_state(
+        geo::standard_map(),
+        [
+            "FRA: F bre -> eng",
+            "FRA: A pic -> bel",
+            "FRA: A bur Supports A pic -> bel",
+            "FRA: F mao Supports F bre -> eng",
+            "ENG: F eng convoys pic -> bel",
+        ]
+        .into_iter()
+        .map(ord)
+        .collect(),
+    );
+    let outcome = disrupted.adjudicate(Rulebook::default());
+    assert_eq!(
+        outcome.move_route(&ord("FRA: A pic -> bel")),
+        Some(MoveRoute::Direct)
+    );
+}
+
+#[test]
+fn outcome_diff_reports_mismatches() {
+    let submission = Submission::with_inferred_state(
+        geo::standard_map(),
+        [
+            "AUS: F tri Hold",
+            "AUS: A vie Supports A ven -> tri",
+            "ITA: A ven -> tri",
+        ]
+        .into_iter()
+        .map(ord)
+        .collect(),
+    );
+    let outcome = submission.adjudicate(Rulebook::default());
+
+    let expected = [
+        ("AUS: F tri Hold", OrderState::Succeeds),
+        ("ITA: A ven -> tri", OrderState::Succeeds),
+        ("ITA: A rom Hold", OrderState::Succeeds),
+    ]
+    .into_iter()
+    .map(|(order, state)| (ord(order), state))
+    .collect();
+
+    let diff = outcome.diff(&expected);
+    assert_eq!(diff.len(), 2);
+
+    assert_eq!(diff[0].order, &ord("ITA: A rom Hold"));
+    assert_eq!(diff[0].actual, None);
+    assert_eq!(diff[0].explanation, None);
+
+    assert_eq!(diff[1].order, &ord("ITA: A ven -> tri"));
+    assert_eq!(diff[1].expected, OrderState::Succeeds);
+    assert_eq!(diff[1].actual, Some(OrderState::Fails));
+    assert_eq!(
+        diff[1].to_string(),
+        "ITA: A ven -> tri: expected Succeeds, got Fails: occupier defended"
+    );
+    assert_eq!(diff[0].to_string(), "ITA: A rom holds: not in outcome");
+}
+
+const TWO_FLEET_CONVOY: &[&str] = &[
+    "ENG: A bre -> nwy",
+    "ENG: F eng convoys bre -> nwy",
+    "ENG: F nth convoys bre -> nwy",
+];
+
+#[test]
+fn max_convoy_length() {
+    let mut rules = Rulebook::default();
+    assert_eq!(
+        order_state_with(rules.clone(), TWO_FLEET_CONVOY, "ENG: A bre -> nwy"),
+        OrderState::Succeeds
+    );
+
+    rules.max_convoy_length = Some(1);
+    assert_eq!(
+        order_state_with(rules, TWO_FLEET_CONVOY, "ENG: A bre -> nwy"),
+        OrderState::Fails
+    );
+}
+
+fn positions(units: &[&str]) -> Vec<UnitPosition<'static, RegionKey>> {
+    units.iter().map(|unit| unit.parse().unwrap()).collect()
+}
+
+/// DATC 6.C.1, 6.G.1, and a dislodgement, resolved together.
+#[test]
+fn positions_after_main_phase() {
+    let submission = Submission::with_inferred_state(
+        geo::standard_map(),
+        [
+            "TUR: F ank -> con",
+            "TUR: A con -> smy",
+            "TUR: A smy -> ank",
+            "ENG: A nwy -> swe",
+            "ENG: F ska convoys nwy -> swe",
+            "RUS: F swe -> nwy",
+            "FRA: A par -> bur",
+            "FRA: A mar Supports A par -> bur",
+            "GER: A bur Hold",
+        ]
+        .into_iter()
+        .map(ord)
+        .collect(),
+    );
+    let outcome = submission.adjudicate(Rulebook::default());
+
+    assert_eq!(
+        outcome.resulting_positions(),
+        positions(&[
+            "TUR: A ank",
+            "FRA: A bur",
+            "TUR: F con",
+            "FRA: A mar",
+            "RUS: F nwy",
+            "ENG: F ska",
+            "TUR: A smy",
+            "ENG: A swe",
+        ])
+    );
+    assert_eq!(outcome.dislodged_positions(), positions(&["GER: A bur"]));
+}
+
+/// Resolve a parsed order's regions against the standard map, using the map's casing.
+fn resolve_regions(order: &MappedMainOrder) -> Result<MappedMainOrder, String> {
+    order.try_map_region(|region| {
+        geo::standard_map()
+            .find_region(&region.short_name().to_lowercase())
+            .map(RegionKey::from)
+            .ok_or_else(|| region.short_name().into_owned())
+    })
+}
+
+#[test]
+fn order_try_map_region() {
+    assert_eq!(
+        resolve_regions(&ord("ENG: F Nth Convoys Lon -> Bel")),
+        Ok(ord("ENG: F nth Convoys lon -> bel"))
+    );
+    assert_eq!(
+        resolve_regions(&ord("ENG: A Lon -> Bel via convoy")),
+        Ok(ord("ENG: A lon -> bel via convoy"))
+    );
+    assert_eq!(
+        resolve_regions(&ord("FRA: A par Supports A Xyz -> bur")),
+        Err("Xyz".to_string())
+    );
+
+    let retreat = "TUR: F ank -> BLA".parse::<MappedRetreatOrder>().unwrap();
+    assert_eq!(
+        retreat.try_map_region(|r| Ok::<_, ()>(RegionKey::new(
+            r.province().short_name().to_lowercase(),
+            r.coast()
+        ))),
+        Ok("TUR: F ank -> bla".parse().unwrap())
+    );
+}
+
+#[test]
+fn unknown_region_suggestions() {
+    let error = ord("ENG: A lon -> bell")
+        .try_map_region(|region| {
+            geo::standard_map()
+                .resolve_region(&region.short_name())
+                .map(RegionKey::from)
+        })
+        .unwrap_err();
+
+    assert_eq!(
+        error.kind(),
+        &ErrorKind::UnknownRegion(vec!["bel".into(), "bal".into(), "ber".into()])
+    );
+}
+
+/// DATC 6.E.13
+#[test]
+fn contest_report_three_way_beleaguered_garrison() {
+    let submission = Submission::with_inferred_state(
+        geo::standard_map(),
+        [
+            "ENG: F edi Supports F yor -> nth",
+            "ENG: F yor -> nth",
+            "FRA: F bel -> nth",
+            "FRA: F eng Supports F bel -> nth",
+            "GER: F nth Hold",
+            "RUS: F nwg -> nth",
+            "RUS: F nwy Supports F nwg -> nth",
+        ]
+        .into_iter()
+        .map(ord)
+        .collect(),
+    );
+    let outcome = submission.adjudicate(Rulebook::default());
+
+    assert_eq!(
+        outcome.contest_report(&"nth".into()),
+        vec![
+            (Nation::from("GER"), 1),
+            (Nation::from("ENG"), 2),
+            (Nation::from("FRA"), 2),
+            (Nation::from("RUS"), 2),
+        ]
+    );
+    assert_eq!(
+        outcome.contest_report(&"yor".into()),
+        vec![(Nation::from("ENG"), 1)]
+    );
+    assert!(outcome.contest_report(&"lon".into()).is_empty());
+}
+
+#[test]
+fn contest_report_head_to_head_and_friendly_fire() {
+    let submission = Submission::with_inferred_state(
+        geo::standard_map(),
+        [
+            "GER: A mun -> bur",
+            "GER: A ruh Supports A mun -> bur",
+            "FRA: A bur -> mun",
+            "FRA: A tyr Supports A bur -> mun",
+            "FRA: A sil Supports A bur -> mun",
+            "ITA: A ven Hold",
+            "ITA: A rom -> ven",
+        ]
+        .into_iter()
+        .map(ord)
+        .collect(),
+    );
+    let outcome = submission.adjudicate(Rulebook::default());
+
+    // The German army lost the head-to-head, but resisted with its defend strength.
+    assert_eq!(
+        outcome.contest_report(&"mun".into()),
+        vec![(Nation::from("GER"), 2), (Nation::from("FRA"), 3)]
+    );
+
+    // A unit can't dislodge one of its own nation, so the Italian move has no strength.
+    assert_eq!(
+        outcome.contest_report(&"ven".into()),
+        vec![(Nation::from("ITA"), 1), (Nation::from("ITA"), 0)]
+    );
+}
+
+#[test]
+fn turn_summary() {
+    let submission = Submission::with_inferred_state(
+        geo::standard_map(),
+        [
+            // Dislodges the German army in Burgundy and cuts its support.
+            "FRA: A par -> bur",
+            "FRA: A mar Supports A par -> bur",
+            "GER: A bur Supports A mun",
+            "GER: A mun Hold",
+            // A bounce between foreign units.
+            "AUS: A vie -> gal",
+            "RUS: A war -> gal",
+            // A bounce against a unit of the same nation.
+            "ITA: A ven -> rom",
+            "ITA: A rom Hold",
+            // A convoy disrupted by dislodgement.
+            "ENG: A lon -> bel",
+            "ENG: F nth convoys lon -> bel",
+            "GER: F hel -> nth",
+            "GER: F den Supports F hel -> nth",
+            // A successful move.
+            "TUR: A con -> bul",
+        ]
+        .into_iter()
+        .map(ord)
+        .collect(),
+    );
+    let outcome = submission.adjudicate(Rulebook::default());
+
+    assert_eq!(
+        outcome.summary(),
+        TurnSummary {
+            successful_moves: 3,
+            bounces: 3,
+            dislodgements: 2,
+            cut_supports: 1,
+            disrupted_convoys: 1,
+        }
+    );
+}
+
+#[test]
+fn validate_command_against_map() {
+    fn validate(order: &str) -> Result<(), IllegalOrder> {
+        let order = ord(order);
+        order
+            .command
+            .validate(geo::standard_map(), &order.region, order.unit_type)
+    }
+
+    assert_eq!(validate("FRA: A par -> bur"), Ok(()));
+    assert_eq!(validate("FRA: A par Hold"), Ok(()));
+    // Reachable only by convoy, though no fleets were ordered.
+    assert_eq!(validate("FRA: A bre -> nwy"), Ok(()));
+    assert_eq!(
+        validate("AUS: A boh -> edi"),
+        Err(IllegalOrder::UnreachableDestination)
+    );
+    assert_eq!(
+        validate("FRA: F bre -> mun"),
+        Err(IllegalOrder::UnreachableDestination)
+    );
+    assert_eq!(
+        validate("FRA: A mar -> lyo"),
+        Err(IllegalOrder::UnreachableDestination)
+    );
+    assert_eq!(
+        validate("FRA: A bre -> nwy via land"),
+        Err(IllegalOrder::UnreachableDestination)
+    );
+}
+
+#[test]
+fn convoy_disrupted_is_distinct_from_no_path() {
+    use diplomacy::judge::{AttackOutcome, OrderOutcome};
+
+    fn move_outcome(orders: &[&str]) -> OrderOutcome<MappedMainOrder> {
+        let orders = orders.iter().copied().map(ord).collect::<Vec<_>>();
+        let submission = Submission::with_inferred_state(geo::standard_map(), orders.clone());
+        let outcome = submission.adjudicate(Rulebook::default());
+        outcome.get(&orders[0]).unwrap().map_order(|o| o.clone())
+    }
+
+    // DATC 6.F.7: the only convoying fleet is dislodged.
+    assert_eq!(
+        move_outcome(&[
+            "ENG: A lon -> hol",
+            "ENG: F nth convoys lon -> hol",
+            "GER: F hel Supports F ska -> nth",
+            "GER: F ska -> nth",
+        ]),
+        OrderOutcome::Move(AttackOutcome::ConvoyDisrupted)
+    );
+
+    // A fleet could have convoyed the army, but was not ordered to.
+    assert_eq!(
+        move_outcome(&["ENG: A lon -> hol", "ENG: F nth Hold"]),
+        OrderOutcome::Move(AttackOutcome::NoPath)
+    );
+}
+
+#[test]
+fn generated_hold_for_unordered_unit() {
+    use diplomacy::StartingPositions;
+
+    let start = ["FRA: A par", "GER: A mun"]
+        .into_iter()
+        .map(unit_pos)
+        .collect::<StartingPositions>();
+    let submission = Submission::new(geo::standard_map(), &start, vec![ord("FRA: A par -> bur")]);
+    let outcome = submission.adjudicate(Rulebook::default());
+
+    assert_eq!(outcome.all_orders().count(), 2);
+    assert!(outcome.all_orders().any(|o| *o == ord("GER: A mun Hold")));
+    assert_eq!(
+        outcome.generated_hold_orders().collect::<Vec<_>>(),
+        vec![&ord("GER: A mun Hold")]
+    );
+}
+
+#[test]
+fn attacks_on_nation() {
+    let orders = [
+        "ENG: A hol Hold",
+        "ENG: F nth Supports A hol",
+        "FRA: A bel -> hol",
+        "FRA: F eng -> lon",
+        "GER: A ruh Supports A bel -> hol",
+        "GER: A kie -> ber",
+    ]
+    .into_iter()
+    .map(ord)
+    .collect::<Vec<_>>();
+    let submission = Submission::with_state(
+        geo::standard_map(),
+        &orders,
+        initial_ownerships(),
+        orders.clone(),
+    );
+    let outcome = submission.adjudicate(Rulebook::default());
+
+    assert_eq!(
+        outcome.attacks_on(&Nation::from("ENG")),
+        vec![
+            &ord("FRA: A bel -> hol"),
+            &ord("FRA: F eng -> lon"),
+            &ord("GER: A ruh Supports A bel -> hol"),
+        ]
+    );
+    assert!(outcome.attacks_on(&Nation::from("FRA")).is_empty());
+    assert!(outcome.attacks_on(&Nation::from("GER")).is_empty());
+}
+
+/// DATC 6.G.8 orders the same adjacent move with and without "via convoy".
+#[test]
+fn declared_convoy_intent() {
+    assert_eq!(
+        ord("ENG: A bel -> hol").declared_convoy_intent(),
+        ConvoyIntent::None
+    );
+    assert_eq!(
+        ord("ENG: A bel -> hol via convoy").declared_convoy_intent(),
+        ConvoyIntent::Mandated
+    );
+    assert_eq!(
+        ord("ENG: A bel -> hol via land").declared_convoy_intent(),
+        ConvoyIntent::Forbidden
+    );
+    assert_eq!(
+        ord("ENG: F nth convoys bel -> hol").declared_convoy_intent(),
+        ConvoyIntent::None
+    );
+}
+
+#[test]
+fn same_action_ignores_convoy_intent() {
+    let plain = ord("ENG: A lon -> bel");
+    let via_convoy = ord("ENG: A lon -> bel via convoy");
+    assert_ne!(plain, via_convoy);
+    assert!(plain.same_action(&via_convoy));
+    assert!(via_convoy.same_action(&ord("ENG: A lon -> bel via land")));
+
+    assert!(!plain.same_action(&ord("ENG: A lon -> hol via convoy")));
+    assert!(!plain.same_action(&ord("FRA: A lon -> bel")));
+    assert!(!plain.same_action(&ord("ENG: A lon Hold")));
+    assert!(ord("ENG: A lon Hold").same_action(&ord("ENG: A lon Hold")));
+}
+
+#[test]
+fn convoyed_move_reports_endpoints() {
+    assert_eq!(
+        ord("TUR: F aeg convoys bul -> con").command.convoyed_move(),
+        Some((&reg("bul"), &reg("con")))
+    );
+    assert_eq!(ord("TUR: F aeg Hold").command.convoyed_move(), None);
+    assert_eq!(ord("TUR: A bul -> con").command.convoyed_move(), None);
+}
+
+#[test]
+fn order_display_styles() {
+    use diplomacy::order::DisplayStyle;
+
+    for (text, judge) in [
+        ("ENG: F lon -> eng", "F LON - ENG"),
+        ("FRA: A par holds", "A PAR H"),
+        ("FRA: A par supports A mar -> bur", "A PAR S A MAR - BUR"),
+        ("FRA: A bur supports F mar", "A BUR S F MAR"),
+        ("ENG: F nth convoys lon -> nwy", "F NTH C A LON - NWY"),
+        ("RUS: F stp(sc) -> bot", "F STP/SC - BOT"),
+        ("ENG: A lon -> bel via convoy", "A LON - BEL"),
+    ] {
+        let order = ord(text);
+        assert_eq!(
+            order.to_string_styled(DisplayStyle::Canonical),
+            order.to_string()
+        );
+        assert_eq!(order.to_string_styled(DisplayStyle::Judge), judge);
+    }
+}
+
+#[test]
+fn turn_events() {
+    use diplomacy::judge::TurnEvent;
+
+    let submission = Submission::with_inferred_state(
+        geo::standard_map(),
+        [
+            // DATC 6.C.1
+            "TUR: F ank -> con",
+            "TUR: A con -> smy",
+            "TUR: A smy -> ank",
+            "ENG: A lon -> nwy",
+            "ENG: F nth convoys lon -> nwy",
+            "FRA: A par -> bur",
+            "GER: A mun -> bur",
+            "AUS: A vie -> gal",
+            "AUS: A bud Supports A vie -> gal",
+            "RUS: A gal Hold",
+        ]
+        .into_iter()
+        .map(ord)
+        .collect(),
+    );
+    let outcome = submission.adjudicate(Rulebook::default());
+
+    assert_eq!(
+        outcome.to_events(),
+        vec![
+            TurnEvent::Move {
+                unit: unit_pos("TUR: F ank"),
+                to: reg("con"),
+            },
+            TurnEvent::Move {
+                unit: unit_pos("TUR: A con"),
+                to: reg("smy"),
+            },
+            TurnEvent::Move {
+                unit: unit_pos("TUR: A smy"),
+                to: reg("ank"),
+            },
+            TurnEvent::ConvoyedMove {
+                unit: unit_pos("ENG: A lon"),
+                to: reg("nwy"),
+                route: vec![reg("nth")],
+            },
+            TurnEvent::Bounce {
+                unit: unit_pos("FRA: A par"),
+                to: reg("bur"),
+            },
+            TurnEvent::Bounce {
+                unit: unit_pos("GER: A mun"),
+                to: reg("bur"),
+            },
+            TurnEvent::Move {
+                unit: unit_pos("AUS: A vie"),
+                to: reg("gal"),
+            },
+            TurnEvent::Dislodged {
+                unit: unit_pos("RUS: A gal"),
+            },
+        ]
+    );
+}
+
+#[test]
+fn sorted_orders_are_canonical() {
+    let orders = vec![
+        "TUR: F bla -> sev",
+        "FRA: A par -> bur",
+        "ENG: F nth convoys lon -> nwy",
+        "FRA: A mar Supports A par -> bur",
+        "ENG: A lon -> nwy",
+        "FRA: F bre Hold",
+    ];
+
+    let sorted = |orders: Vec<&str>| {
+        let submission = Submission::with_inferred_state(
+            geo::standard_map(),
+            orders.into_iter().map(ord).collect(),
+        );
+        submission
+            .sorted_orders()
+            .into_iter()
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+
+    let forward = sorted(orders.clone());
+    let backward = sorted(orders.into_iter().rev().collect());
+    assert_eq!(forward, backward);
+    assert!(forward.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(
+        forward,
+        vec![
+            ord("ENG: A lon -> nwy"),
+            ord("ENG: F nth convoys lon -> nwy"),
+            ord("FRA: F bre Hold"),
+            ord("FRA: A mar Supports A par -> bur"),
+            ord("FRA: A par -> bur"),
+            ord("TUR: F bla -> sev"),
+        ]
+    );
+}
+
+/// A variant where the unit in a fortified province is harder to dislodge.
+struct Fortress(&'static str, Rulebook);
+
+impl Adjudicate for Fortress {
+    fn adjudicate<'a>(
+        &self,
+        context: &Context<'a, impl Adjudicate>,
+        resolver: &mut ResolverState<'a>,
+        order: &'a MappedMainOrder,
+    ) -> OrderState {
+        self.1.adjudicate(context, resolver, order)
+    }
+
+    fn explain<'a>(
+        &self,
+        context: &Context<'a, impl Adjudicate>,
+        resolver: &mut ResolverState<'a>,
+        order: &'a MappedMainOrder,
+    ) -> OrderOutcome<&'a MappedMainOrder> {
+        self.1.explain(context, resolver, order)
+    }
+
+    fn policies(&self) -> &Rulebook {
+        &self.1
+    }
+
+    fn defense_bonus(&self, order: &MappedMainOrder) -> usize {
+        usize::from(order.region.province().short_name() == self.0)
+    }
+}
+
+#[test]
+fn defense_bonus_resists_supported_attack() {
+    let orders = vec![
+        ord("GER: A mun -> bur"),
+        ord("GER: A ruh Supports A mun -> bur"),
+        ord("FRA: A bur Hold"),
+    ];
+    let submission = Submission::with_inferred_state(geo::standard_map(), orders.clone());
+
+    let outcome = submission.adjudicate(Rulebook::default());
+    assert_eq!(
+        outcome.get(&orders[0]).map(OrderState::from),
+        Some(OrderState::Succeeds)
+    );
+
+    let outcome = submission.adjudicate(Fortress("bur", Rulebook::default()));
+    assert_eq!(
+        outcome.get(&orders[0]).map(OrderState::from),
+        Some(OrderState::Fails)
+    );
+    assert_eq!(
+        outcome.get(&orders[2]).map(OrderState::from),
+        Some(OrderState::Succeeds)
+    );
+}
+
+#[test]
+fn contest_report_includes_defense_bonus() {
+    let submission = Submission::with_inferred_state(
+        geo::standard_map(),
+        vec![
+            ord("GER: A mun -> bur"),
+            ord("GER: A ruh Supports A mun -> bur"),
+            ord("FRA: A bur Hold"),
+        ],
+    );
+
+    let outcome = submission.adjudicate(Fortress("bur", Rulebook::default()));
+    assert_eq!(
+        outcome.contest_report(&"bur".into()),
+        vec![(Nation::from("FRA"), 2), (Nation::from("GER"), 2)]
+    );
+}
+
+/// Check that every order reported by a phase outcome can be looked up, and count them.
+fn count_phase_outcomes<P>(outcome: &P) -> usize
+where
+    P: PhaseOutcome,
+    P::OrderOutcome: PartialEq + std::fmt::Debug,
+{
+    outcome
+        .order_outcomes()
+        .inspect(|(order, order_outcome)| {
+            assert_eq!(outcome.get(order), Some(*order_outcome));
+        })
+        .count()
+}
+
+#[test]
+fn phase_outcomes_are_uniform() {
+    let submission = Submission::with_inferred_state(
+        geo::standard_map(),
+        vec![
+            ord("AUS: A bud Supports A tri -> vie"),
+            ord("AUS: A tri -> vie"),
+            ord("ITA: A vie Hold"),
+        ],
+    );
+    let main_outcome = submission.adjudicate(Rulebook::default());
+    assert_eq!(count_phase_outcomes(&main_outcome), 3);
+
+    let start = main_outcome.to_retreat_start();
+    let retreat_orders = vec![retreat_ord("ITA: A vie -> tyr")];
+    let retreat_context = diplomacy::judge::retreat::Context::new(&start, retreat_orders);
+    let retreat_outcome = retreat_context.resolve();
+    assert_eq!(count_phase_outcomes(&retreat_outcome), 1);
+
+    let world = world::TestWorld::empty().with_unit("AUS: A bud");
+    let last_time = initial_ownerships();
+    let build_orders = vec![build_ord("AUS: A vie build"), build_ord("AUS: F tri build")];
+    let build_context = diplomacy::judge::build::Context::new(
+        geo::standard_map(),
+        &last_time,
+        &world,
+        build_orders,
+    );
+    let build_outcome = build_context.resolve();
+    assert_eq!(count_phase_outcomes(&build_outcome), 2);
+}
+
+#[test]
+fn adjacency_fixture_roundtrip() {
+    let text = include_str!("fixtures/adjacency.txt");
+    let map = geo::builder::from_adjacency_reader(text.as_bytes()).unwrap();
+
+    assert_eq!(map.provinces().count(), 5);
+    assert_eq!(map.regions().count(), 7);
+    assert_eq!(
+        map.find_border_between(&reg("nor"), &reg("sou"))
+            .map(|b| b.terrain()),
+        Some(Terrain::Land)
+    );
+    assert_eq!(
+        map.initial_ownership().get(&ProvinceKey::new("sou")),
+        Some(&Nation::from("TWO"))
+    );
+    assert_eq!(map.neutral_supply_centers().len(), 2);
+    assert_eq!(map.to_adjacency(), text);
+}
+
+#[test]
+fn standard_map_adjacency_roundtrip() {
+    let text = geo::standard_map().to_adjacency();
+    let map = geo::builder::from_adjacency_reader(text.as_bytes()).unwrap();
+    assert_eq!(map.to_adjacency(), text);
+    assert_eq!(map.to_dot(), geo::standard_map().to_dot());
+}
+
+#[test]
+fn adjacency_errors_report_line() {
+    let read = |text: &str| geo::builder::from_adjacency_reader(text.as_bytes()).unwrap_err();
+
+    let error = read("# Two provinces\nlan land sou\nsou land\n");
+    assert_eq!(error.line(), 2);
+    assert!(matches!(
+        error.kind(),
+        AdjacencyErrorKind::AsymmetricBorder(r) if r == "sou"
+    ));
+
+    let error = read("lan land sou\nsou land lan nor\n");
+    assert_eq!(error.line(), 2);
+    assert!(matches!(
+        error.kind(),
+        AdjacencyErrorKind::UnknownNeighbor(r) if r == "nor"
+    ));
+
+    let error = read("lan land sou:sea\nsou coast lan:sea\n");
+    assert_eq!(error.line(), 1);
+    assert!(matches!(error.kind(), AdjacencyErrorKind::Map(_)));
+
+    let error = read("lan land\nlan mud\n");
+    assert_eq!(error.line(), 2);
+    assert!(matches!(
+        error.kind(),
+        AdjacencyErrorKind::DuplicateRegion(_)
+    ));
+}
+
+/// DATC 6.D.2
+#[test]
+fn cut_supports_for_hold() {
+    let orders = vec![
+        ord("AUS: F adr Supports A tri -> ven"),
+        ord("AUS: A tri -> ven"),
+        ord("AUS: A vie -> tyr"),
+        ord("ITA: A ven Hold"),
+        ord("ITA: A tyr Supports A ven"),
+    ];
+    let submission = Submission::with_inferred_state(geo::standard_map(), orders.clone());
+    let outcome = submission.adjudicate(Rulebook::default());
+
+    assert_eq!(
+        outcome.cut_supports_for(&orders[3]),
+        vec![(&orders[4], &orders[2])]
+    );
+    assert!(outcome.cut_supports_for(&orders[1]).is_empty());
+}
+
+#[test]
+fn board_at_rest_is_static() {
+    let positions = vec![unit_pos("GER: A mun"), unit_pos("FRA: A bur")];
+    assert!(is_static(geo::standard_map(), &positions, vec![]));
+    assert!(is_static(
+        geo::standard_map(),
+        &positions,
+        vec![ord("FRA: A bur -> mun")]
+    ));
+    assert!(!is_static(
+        geo::standard_map(),
+        &positions,
+        vec![ord("FRA: A bur -> par")]
+    ));
+}
+
+#[test]
+fn mutually_supporting_pair_is_stalemate_line() {
+    let defense = [
+        ord("GER: A mun Supports A ber"),
+        ord("GER: A ber Supports A mun"),
+    ];
+    let mut positions = vec![
+        unit_pos("GER: A mun"),
+        unit_pos("GER: A ber"),
+        unit_pos("FRA: A bur"),
+        unit_pos("FRA: A ruh"),
+    ];
+    assert!(is_stalemate_line(geo::standard_map(), &positions, &defense));
+
+    // Silesia borders both defenders, so it can cut Berlin's support while Burgundy and
+    // Ruhr attack Munich.
+    positions.push(unit_pos("FRA: A sil"));
+    assert!(!is_stalemate_line(
+        geo::standard_map(),
+        &positions,
+        &defense
+    ));
+}
+
+#[test]
+fn counterfactual_without_key_support() {
+    let submission = Submission::with_inferred_state(
+        geo::standard_map(),
+        vec![
+            ord("FRA: A bur -> mun"),
+            ord("ITA: A tyr Supports A bur -> mun"),
+            ord("GER: A mun Hold"),
+        ],
+    );
+    let attack = ord("FRA: A bur -> mun");
+    let outcome = submission.adjudicate(Rulebook::default());
+    assert_eq!(
+        outcome.get(&attack).map(OrderState::from),
+        Some(OrderState::Succeeds)
+    );
+
+    let without_support = outcome.counterfactual(&ord("ITA: A tyr Supports A bur -> mun"));
+    let counterfactual = without_support.adjudicate(Rulebook::default());
+    assert_eq!(
+        counterfactual.get(&attack).map(OrderState::from),
+        Some(OrderState::Fails)
+    );
+    assert_eq!(
+        counterfactual
+            .get(&ord("ITA: A tyr Hold"))
+            .map(OrderState::from),
+        Some(OrderState::Succeeds)
+    );
+    assert_eq!(
+        outcome.counterfactual_state(
+            &ord("ITA: A tyr Supports A bur -> mun"),
+            &attack,
+            Rulebook::default()
+        ),
+        Some(OrderState::Fails)
+    );
+}
+
+#[test]
+fn circular_move_chains() {
+    // DATC 6.C.1
+    let rotation = Submission::with_inferred_state(
+        geo::standard_map(),
+        vec![
+            ord("TUR: F ank -> con"),
+            ord("TUR: A con -> smy"),
+            ord("TUR: A smy -> ank"),
+            ord("TUR: A bul Hold"),
+        ],
+    );
+    let outcome = rotation.adjudicate(Rulebook::default());
+    assert_eq!(
+        outcome.circular_move_chains(),
+        vec![vec![
+            &ord("TUR: F ank -> con"),
+            &ord("TUR: A con -> smy"),
+            &ord("TUR: A smy -> ank"),
+        ]]
+    );
+
+    // DATC 6.C.3: the circle is broken by a bounce in constantinople.
+    let disrupted = Submission::with_inferred_state(
+        geo::standard_map(),
+        vec![
+            ord("TUR: F ank -> con"),
+            ord("TUR: A con -> smy"),
+            ord("TUR: A smy -> ank"),
+            ord("TUR: A bul -> con"),
+        ],
+    );
+    let outcome = disrupted.adjudicate(Rulebook::default());
+    assert!(outcome.circular_move_chains().is_empty());
+}
+
+#[test]
+fn redundant_supports_for_oversupported_move() {
+    let submission = Submission::with_inferred_state(
+        geo::standard_map(),
+        vec![
+            ord("FRA: A bur -> mun"),
+            ord("FRA: A ruh Supports A bur -> mun"),
+            ord("ITA: A tyr Supports A bur -> mun"),
+            ord("FRA: F bre Supports F gas"),
+            ord("FRA: F gas Hold"),
+            ord("GER: A mun Hold"),
+        ],
+    );
+    let outcome = submission.adjudicate(Rulebook::default());
+    assert_eq!(
+        outcome.redundant_supports(),
+        vec![
+            &ord("FRA: A ruh Supports A bur -> mun"),
+            &ord("ITA: A tyr Supports A bur -> mun"),
+            &ord("FRA: F bre Supports F gas"),
+        ]
+    );
+}
+
+#[test]
+fn needed_support_is_not_redundant() {
+    let submission = Submission::with_inferred_state(
+        geo::standard_map(),
+        vec![
+            ord("FRA: A bur -> mun"),
+            ord("ITA: A tyr Supports A bur -> mun"),
+            ord("GER: A mun Hold"),
+        ],
+    );
+    let outcome = submission.adjudicate(Rulebook::default());
+    assert!(outcome.redundant_supports().is_empty());
+}
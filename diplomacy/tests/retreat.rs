@@ -0,0 +1,141 @@
+#![cfg(test)]
+
+#[path = "./util.rs"]
+mod util;
+
+use diplomacy::judge::retreat::{Context, OrderOutcome};
+use diplomacy::judge::OrderState::*;
+use diplomacy::judge::{OrderState, Rulebook};
+use util::*;
+
+/// Same setup as DATC 6.H.6. The dislodged army in Vienna cannot retreat to Bohemia
+/// because it was contested, to Trieste because its attacker came from there, or to
+/// Budapest because it is occupied.
+#[test]
+fn retreat_options_exclude_invalid_destinations() {
+    let (submission, expected) = submit_main_phase! {
+       "AUS: A bud Supports A tri -> vie",
+       "AUS: A tri -> vie": Succeeds,
+       "GER: A mun -> boh": Fails,
+       "GER: A sil -> boh": Fails,
+       "ITA: A vie Hold": Fails,
+    };
+
+    let outcome = resolve_main!(submission, expected);
+    let options = outcome.retreat_options();
+
+    assert_eq!(options.len(), 1);
+    assert_eq!(
+        options.get(&ord("ITA: A vie Hold").unit_position()),
+        Some(&vec![reg("gal"), reg("tyr")])
+    );
+}
+
+/// Two units are dislodged in separate battles and retreat to different vacant provinces.
+/// Both retreats succeed and the resulting board reflects the new positions.
+#[test]
+fn resulting_positions_after_non_conflicting_retreats() {
+    let (submission, expected) = submit_main_phase! {
+       "AUS: A bud Supports A tri -> vie",
+       "AUS: A tri -> vie": Succeeds,
+       "ITA: A vie Hold": Fails,
+       "GER: A ruh Supports A mun -> bur",
+       "GER: A mun -> bur": Succeeds,
+       "FRA: A bur Hold": Fails,
+    };
+
+    let outcome = resolve_main!(submission, expected);
+    let start = outcome.to_retreat_start();
+    let context = Context::new(
+        &start,
+        vec!["ITA: A vie -> tyr", "FRA: A bur -> pic"]
+            .into_iter()
+            .map(retreat_ord),
+    );
+    let retreat_outcome = context.resolve();
+
+    for (order, outcome) in retreat_outcome.order_outcomes() {
+        assert_eq!(*outcome, OrderOutcome::Moves, "{}", order);
+    }
+
+    assert_eq!(
+        retreat_outcome.resulting_positions(),
+        vec![
+            unit_pos("AUS: A bud"),
+            unit_pos("GER: A bur"),
+            unit_pos("FRA: A pic"),
+            unit_pos("GER: A ruh"),
+            unit_pos("ITA: A tyr"),
+            unit_pos("AUS: A vie"),
+        ]
+    );
+}
+
+/// Same setup as DATC 6.H.6, but Italy orders the dislodged army twice. The first order is
+/// used and the second is discarded.
+#[test]
+fn second_retreat_to_same_unit_is_discarded() {
+    let (submission, expected) = submit_main_phase! {
+       "AUS: A bud Supports A tri -> vie",
+       "AUS: A tri -> vie": Succeeds,
+       "ITA: A vie Hold": Fails,
+    };
+
+    let outcome = resolve_main!(submission, expected);
+    let start = outcome.to_retreat_start();
+    let first = retreat_ord("ITA: A vie -> tyr");
+    let second = retreat_ord("ITA: A vie -> gal");
+    let context = Context::new(&start, vec![first.clone(), second.clone()]);
+    let retreat_outcome = context.resolve();
+
+    assert_eq!(retreat_outcome.get(&first), Some(&OrderOutcome::Moves));
+    assert_eq!(
+        retreat_outcome.get(&second),
+        Some(&OrderOutcome::MultipleToSameUnit)
+    );
+    assert_eq!(
+        retreat_outcome.resulting_positions(),
+        vec![
+            unit_pos("AUS: A bud"),
+            unit_pos("ITA: A tyr"),
+            unit_pos("AUS: A vie")
+        ]
+    );
+}
+
+/// The dislodged army in Munich is surrounded except for Silesia, so it retreats there
+/// without being ordered to.
+#[test]
+fn adjudicate_through_retreats_takes_only_retreat() {
+    let (submission, expected) = submit_main_phase! {
+       "FRA: A bur -> mun": Succeeds,
+       "FRA: A ruh Supports A bur -> mun",
+       "GER: A mun Hold": Fails,
+       "GER: A ber Hold",
+       "GER: A kie Hold",
+       "AUS: A boh Hold",
+       "ITA: A tyr Hold",
+    };
+
+    let (outcome, report) = submission.adjudicate_through_retreats(Rulebook::default());
+    for (order, state) in &expected {
+        assert_eq!(outcome.get(order).map(OrderState::from), Some(*state));
+    }
+    assert_eq!(outcome.dislodged_positions(), vec![unit_pos("GER: A mun")]);
+    assert_eq!(
+        report.orders.get(&retreat_ord("GER: A mun -> sil")),
+        Some(&OrderOutcome::Moves)
+    );
+    assert_eq!(
+        report.resulting_positions,
+        vec![
+            unit_pos("GER: A ber"),
+            unit_pos("AUS: A boh"),
+            unit_pos("GER: A kie"),
+            unit_pos("FRA: A mun"),
+            unit_pos("FRA: A ruh"),
+            unit_pos("GER: A sil"),
+            unit_pos("ITA: A tyr"),
+        ]
+    );
+}
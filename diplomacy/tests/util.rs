@@ -45,7 +45,7 @@ macro_rules! submit_main_phase {
 #[macro_export]
 macro_rules! resolve_main {
     ($context:expr, $expectation:expr) => {{
-        let outcome = $context.adjudicate(::diplomacy::judge::Rulebook);
+        let outcome = $context.adjudicate(::diplomacy::judge::Rulebook::default());
 
         // We refer back to the submitted orders to ensure we visit orders in the same
         // order across test runs. This makes output diffing easier.
@@ -64,6 +64,20 @@ macro_rules! resolve_main {
             }
         }
 
+        // Resolving independent clusters of orders separately must not change any outcome.
+        let partitioned = $context.adjudicate_partitioned(::diplomacy::judge::Rulebook::default());
+        for order in outcome.all_orders() {
+            assert_eq!(outcome.get(order), partitioned.get(order), "{}", order);
+        }
+
+        #[cfg(feature = "parallel")]
+        {
+            let parallel = $context.adjudicate_parallel(::diplomacy::judge::Rulebook::default());
+            for order in outcome.all_orders() {
+                assert_eq!(outcome.get(order), parallel.get(order), "{}", order);
+            }
+        }
+
         outcome
     }};
 }
@@ -177,11 +191,15 @@ pub fn get_results(orders: Vec<&str>) -> HashMap<MappedMainOrder, OrderState> {
     let parsed = orders.into_iter().map(ord).collect::<Vec<_>>();
     let ctx = diplomacy::judge::Submission::with_inferred_state(geo::standard_map(), parsed);
 
-    let out = ctx.adjudicate(Rulebook);
+    let out = ctx.adjudicate(Rulebook::default());
     for o in ctx.submitted_orders() {
         println!("{:?}: {:?}", o, out.get(o).unwrap());
     }
 
+    if let Err(violations) = out.check_invariants() {
+        panic!("Adjudication violated invariants: {:#?}", violations);
+    }
+
     out.into()
 }
 
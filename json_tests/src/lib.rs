@@ -6,12 +6,17 @@
 
 #[cfg(test)]
 mod tests {
-    use std::{fmt::Display, str::FromStr};
+    use std::{collections::HashMap, fmt::Display, str::FromStr};
 
     use anyhow::Context;
     use diplomacy::{
-        geo::standard_map,
-        judge::{MappedBuildOrder, MappedMainOrder, Rulebook, Submission},
+        geo::{standard_map, RegionKey},
+        judge::{
+            build, retreat, GameState, IllegalOrder, MappedBuildOrder, MappedMainOrder,
+            MappedRetreatOrder, OrderOutcome, OrderState, Rulebook, Submission, SubmissionSnapshot,
+            TurnEvent,
+        },
+        Phase, Season, Time, UnitPosition,
     };
     use serde::{de::DeserializeOwned, ser::Serializer, Serialize};
 
@@ -67,17 +72,158 @@ mod tests {
         .collect();
 
         let submission = Submission::with_inferred_state(standard_map(), orders);
-        let outcome = submission.adjudicate(Rulebook);
+        let outcome = submission.adjudicate(Rulebook::default());
         let mut ser = serde_json::Serializer::pretty(std::io::stdout());
         ser.collect_seq(outcome.all_orders_with_outcomes()).unwrap();
     }
 
+    #[test]
+    fn roundtrip_illegal_outcome() {
+        let order: MappedMainOrder = "GER: F lon -> nth".parse().unwrap();
+        let submission = Submission::new(
+            standard_map(),
+            &vec!["ENG: F lon".parse::<UnitPosition<RegionKey>>().unwrap()],
+            vec![order.clone()],
+        );
+        let outcome = submission.adjudicate(Rulebook::default());
+
+        let serialized = serde_json::to_string(outcome.get(&order).unwrap()).unwrap();
+        let deserialized: OrderOutcome<MappedMainOrder> =
+            serde_json::from_str(&serialized).unwrap();
+        assert_eq!(
+            deserialized,
+            OrderOutcome::Illegal(IllegalOrder::ForeignUnit)
+        );
+    }
+
+    #[test]
+    fn roundtrip_turn_events() {
+        let orders: Vec<MappedMainOrder> = vec![
+            "ENG: A lon -> nwy",
+            "ENG: F nth convoys lon -> nwy",
+            "FRA: A par -> bur",
+            "GER: A mun -> bur",
+        ]
+        .into_iter()
+        .map(|ord| ord.parse().unwrap())
+        .collect();
+
+        let submission = Submission::with_inferred_state(standard_map(), orders);
+        let events = submission.adjudicate(Rulebook::default()).to_events();
+        assert_eq!(events.len(), 3);
+
+        let serialized = serde_json::to_string(&events).unwrap();
+        let deserialized: Vec<TurnEvent> = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, events);
+    }
+
+    #[test]
+    fn roundtrip_submission_snapshot() {
+        let mut snapshot = SubmissionSnapshot::standard(
+            [
+                "AUS: F tri Hold",
+                "AUS: A vie Supports A ven -> tri",
+                "ITA: A ven -> tri",
+                "ITA: A tyr Supports A ven -> tri",
+                "ITA: F adr Supports A ven -> tri",
+                "ITA: A rom -> bur",
+            ]
+            .into_iter()
+            .map(|ord| ord.parse().unwrap())
+            .collect(),
+        );
+        snapshot.units = Some(
+            [
+                "AUS: F tri",
+                "AUS: A vie",
+                "ITA: A ven",
+                "ITA: A tyr",
+                "ITA: F adr",
+                "ITA: A rom",
+                "ITA: F nap",
+            ]
+            .into_iter()
+            .map(|unit| unit.parse().unwrap())
+            .collect(),
+        );
+
+        let serialized = serde_json::to_string_pretty(&snapshot).unwrap();
+        let replayed: SubmissionSnapshot = serde_json::from_str(&serialized).unwrap();
+
+        let original = snapshot.to_submission().unwrap();
+        let replayed_submission = replayed.to_submission().unwrap();
+        let expected: HashMap<MappedMainOrder, OrderState> =
+            original.adjudicate(snapshot.rules.clone()).into();
+        let actual: HashMap<MappedMainOrder, OrderState> = replayed_submission
+            .adjudicate(replayed.rules.clone())
+            .into();
+
+        assert_eq!(expected, actual);
+        assert_eq!(expected.len(), 8);
+    }
+
+    #[test]
+    fn roundtrip_game_state() {
+        let orders: Vec<MappedMainOrder> = vec![
+            "GER: A mun -> bur",
+            "GER: A ruh Supports A mun -> bur",
+            "GER: A kie -> hol",
+            "FRA: A bur Hold",
+        ]
+        .into_iter()
+        .map(|ord| ord.parse().unwrap())
+        .collect();
+
+        let map = standard_map();
+        let ownerships = map.initial_ownership();
+        let submission = Submission::with_state(map, &orders, ownerships.clone(), orders.clone());
+        let outcome = submission.adjudicate(Rulebook::default());
+        let start = outcome.to_retreat_start();
+        let retreat_order: MappedRetreatOrder = "FRA: A bur -> bel".parse().unwrap();
+        let retreat_context = retreat::Context::new(&start, vec![retreat_order]);
+        let retreat_outcome = retreat_context.resolve();
+
+        // The retreat ended a fall turn, so supply centers change hands, including Belgium,
+        // which the dislodged French army retreated into.
+        let winter = Time::new(Season::Winter, 1901, Phase::Build);
+        let state = GameState::from_retreat(map, &retreat_outcome, &ownerships, winter);
+        let serialized = serde_json::to_string_pretty(&state).unwrap();
+        let roundtripped: GameState = serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(state, roundtripped);
+        assert_eq!(roundtripped.world_map(), Some(map));
+        assert_eq!(roundtripped.positions.len(), 4);
+        let owner = |province: &str| roundtripped.ownerships.get(&province.into()).cloned();
+        assert_eq!(owner("bel"), Some("FRA".into()));
+        assert_eq!(owner("hol"), Some("GER".into()));
+        assert_eq!(owner("mun"), Some("GER".into()));
+        assert_eq!(owner("par"), Some("FRA".into()));
+        assert_eq!(roundtripped.ownerships.len(), 24);
+
+        // After a spring turn, ownership is unchanged.
+        let fall = Time::new(Season::Fall, 1901, Phase::Main);
+        let state = GameState::from_retreat(map, &retreat_outcome, &ownerships, fall);
+        assert_eq!(state.ownerships, ownerships);
+
+        // The build phase that follows starts from the captured ownerships.
+        let build_order: MappedBuildOrder = "FRA: A par build".parse().unwrap();
+        let build_context =
+            build::Context::from_previous(map, &ownerships, &retreat_outcome, vec![build_order]);
+        let build_outcome = build_context.resolve();
+        let spring = Time::new(Season::Spring, 1902, Phase::Main);
+        let state =
+            GameState::from_build(map, &build_outcome, build_context.to_ownerships(), spring);
+        assert_eq!(state.positions.len(), 5);
+        assert_eq!(state.ownerships, roundtripped.ownerships);
+    }
+
     #[test]
     fn roundtrip_main_orders() {
         roundtrip_orders::<MappedMainOrder>(vec![
             "TUR: F ank hold",
             "TUR: A bul -> con",
             "TUR: A bul -> con via convoy",
+            "TUR: A bul -> con via land",
             "TUR: F aeg convoys bul -> con",
             "TUR: A rum supports A bul -> con",
             "AUS: A tri -> ser",
@@ -95,6 +241,16 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn roundtrip_retreat_orders() {
+        roundtrip_orders::<MappedRetreatOrder>(vec![
+            "TUR: F ank -> bla",
+            "TUR: F ank hold",
+            "TUR: F ank disband",
+            "RUS: F bot -> stp(sc)",
+        ]);
+    }
+
     #[test]
     fn roundtrip_build_orders() {
         roundtrip_orders::<MappedBuildOrder>(vec![